@@ -0,0 +1,57 @@
+// Clippy configurations
+#![allow(clippy::needless_return)]
+
+//! Times solving every board in the `boards` dir
+//!
+//! Run with `cargo bench`. Each board is parsed once, then solved from a
+//! fresh copy repeatedly for a few seconds. The median and 10% and 90%
+//! quantile times are printed in microseconds
+
+use std::{fs, time};
+
+use rsudoku::SudokuBoard;
+
+/// How long to spend solving each board
+const DURATION_PER_BOARD: time::Duration = time::Duration::from_secs(3);
+
+/// Returns the time at `quantile` of sorted times
+fn quantile(sorted_times: &[f64], quantile: f64) -> f64 {
+    let index = (quantile * (sorted_times.len() - 1) as f64).round() as usize;
+    return sorted_times[index];
+}
+
+fn main() {
+    let mut board_paths: Vec<_> = fs::read_dir("boards")
+        .expect("Run from the project root, so `boards` can be found")
+        .map(|entry| entry.expect("Couldn't read `boards` dir").path())
+        .collect();
+    board_paths.sort();
+
+    println!("board\t\ttrials\t10%\tmedian\t90%");
+
+    for path in board_paths {
+        let board = SudokuBoard::from_file(&path).expect("Couldn't load board");
+
+        let mut times = Vec::new();
+        let end = time::Instant::now() + DURATION_PER_BOARD;
+
+        while time::Instant::now() < end {
+            let mut board_to_solve = board.clone();
+
+            let start = time::Instant::now();
+            board_to_solve.solve();
+            times.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+        }
+
+        times.sort_unstable_by(f64::total_cmp);
+
+        println!(
+            "{:<12}\t{}\t{:.1}µs\t{:.1}µs\t{:.1}µs",
+            path.file_name().unwrap().to_string_lossy(),
+            times.len(),
+            quantile(&times, 0.1),
+            quantile(&times, 0.5),
+            quantile(&times, 0.9),
+        );
+    }
+}
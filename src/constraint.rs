@@ -0,0 +1,426 @@
+//! Extra rules a sudoku can have on top of its rows, columns, and boxes,
+//! for variants like killer or X sudoku's
+
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{Geometry, SudokuBoard, SudokuError};
+
+/// A rule the solution has to follow, as well as every row, column, and
+/// box having each value once. Boards keep a list of them, added with
+/// `SudokuBoard::add_constraint`
+///
+/// A constraint can do this in two ways, and can use both:
+///
+/// - Regions are groups of spaces that can't have a value twice. They
+///   work like rows: a known value is removed from the rest of its
+///   regions, and a region with a space for every value is checked for
+///   hidden singles
+/// - Eliminations are possible values the constraint rules out, looked
+///   for whenever the board changes, until there are none left
+///
+/// Points are (row, column), starting at 0
+///
+/// ```
+/// use rsudoku::{Constraint, Geometry, SudokuBoard};
+///
+/// /// The corners of the board can't be even
+/// #[derive(Debug)]
+/// struct OddCorners;
+///
+/// impl Constraint for OddCorners {
+///     fn eliminations(&self, board: &SudokuBoard) -> Vec<((usize, usize), usize)> {
+///         let last = board.geometry().size() - 1;
+///         let mut eliminations = Vec::new();
+///
+///         for corner in [(0, 0), (0, last), (last, 0), (last, last)] {
+///             for value in board.candidates(corner) {
+///                 if value % 2 == 0 {
+///                     eliminations.push((corner, value));
+///                 }
+///             }
+///         }
+///
+///         return eliminations;
+///     }
+/// }
+///
+/// let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+/// board.add_constraint(OddCorners).unwrap();
+///
+/// assert!(board.solve());
+/// assert_eq!(board.value((0, 0)).unwrap() % 2, 1);
+/// ```
+pub trait Constraint: fmt::Debug + Send + Sync {
+    /// Returns groups of spaces that can't have the same value twice
+    fn regions(&self, _geometry: &Geometry) -> Vec<Vec<(usize, usize)>> {
+        return Vec::new();
+    }
+
+    /// Returns the spaces the constraint looks at for its eliminations, so
+    /// they can be checked when it's added. The spaces of its regions are
+    /// checked anyway
+    fn spaces(&self) -> Vec<(usize, usize)> {
+        return Vec::new();
+    }
+
+    /// Returns possible values of spaces that the constraint rules out, as
+    /// (point, value). Ruling out the value of a known space means the
+    /// board can't follow the constraint, so it's unsolvable
+    ///
+    /// Values that are already ruled out are skipped, so these don't have
+    /// to be new
+    fn eliminations(&self, _board: &SudokuBoard) -> Vec<((usize, usize), usize)> {
+        return Vec::new();
+    }
+}
+
+/// A group of spaces that can't have the same value twice, like the cages
+/// of a killer sudoku. For the diagonals of an X sudoku, see
+/// `SudokuBoard::set_diagonal`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    spaces: Vec<(usize, usize)>,
+}
+
+impl Region {
+    /// Makes a region of the spaces
+    pub fn new(spaces: Vec<(usize, usize)>) -> Region {
+        return Region { spaces };
+    }
+}
+
+impl Constraint for Region {
+    fn regions(&self, _geometry: &Geometry) -> Vec<Vec<(usize, usize)>> {
+        return vec![self.spaces.clone()];
+    }
+}
+
+/// Two spaces whose values have to pass a test, like one being less than
+/// the other
+///
+/// ```
+/// use rsudoku::{Relation, SudokuBoard};
+///
+/// let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+/// board.add_constraint(Relation::less_than((0, 0), (0, 1))).unwrap();
+/// board.add_constraint(Relation::new((0, 1), (0, 2), |first, second| first == second * 2)).unwrap();
+///
+/// assert!(board.solve());
+/// assert!(board.value((0, 0)) < board.value((0, 1)));
+/// assert_eq!(board.value((0, 1)).unwrap(), board.value((0, 2)).unwrap() * 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Relation {
+    first: (usize, usize),
+    second: (usize, usize),
+
+    /// If the first space can be the first value while the second space is
+    /// the second
+    allowed: fn(usize, usize) -> bool,
+}
+
+impl Relation {
+    /// Makes a relation where `allowed` says which values the spaces can
+    /// have together
+    pub fn new(
+        first: (usize, usize),
+        second: (usize, usize),
+        allowed: fn(usize, usize) -> bool,
+    ) -> Relation {
+        return Relation {
+            first,
+            second,
+            allowed,
+        };
+    }
+
+    /// The first space has a smaller value than the second
+    pub fn less_than(smaller: (usize, usize), larger: (usize, usize)) -> Relation {
+        return Relation::new(smaller, larger, |smaller, larger| smaller < larger);
+    }
+
+    /// The spaces have values one apart
+    pub fn consecutive(first: (usize, usize), second: (usize, usize)) -> Relation {
+        return Relation::new(first, second, |first, second| first.abs_diff(second) == 1);
+    }
+}
+
+impl Constraint for Relation {
+    fn spaces(&self) -> Vec<(usize, usize)> {
+        return vec![self.first, self.second];
+    }
+
+    /// Rules out values of each space that no value of the other space
+    /// works with
+    fn eliminations(&self, board: &SudokuBoard) -> Vec<((usize, usize), usize)> {
+        let first_values = board.candidates(self.first);
+        let second_values = board.candidates(self.second);
+
+        let first_eliminations = first_values
+            .iter()
+            .filter(|&&first| {
+                !second_values
+                    .iter()
+                    .any(|&second| (self.allowed)(first, second))
+            })
+            .map(|&first| (self.first, first));
+
+        let second_eliminations = second_values
+            .iter()
+            .filter(|&&second| {
+                !first_values
+                    .iter()
+                    .any(|&first| (self.allowed)(first, second))
+            })
+            .map(|&second| (self.second, second));
+
+        return first_eliminations.chain(second_eliminations).collect();
+    }
+}
+
+/// Spaces whose values add up to a total. The spaces can have the same
+/// value, unless they're also in a `Region`, or the same row, column, or
+/// box
+///
+/// ```
+/// use rsudoku::{Region, Sum, SudokuBoard};
+///
+/// // A killer cage
+/// let cage = vec![(0, 0), (0, 1), (1, 0)];
+///
+/// let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+/// board.add_constraint(Region::new(cage.clone())).unwrap();
+/// board.add_constraint(Sum::new(cage, 6)).unwrap();
+///
+/// assert!(board.solve());
+/// assert_eq!(board.value((0, 0)).unwrap() + board.value((0, 1)).unwrap() + board.value((1, 0)).unwrap(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sum {
+    spaces: Vec<(usize, usize)>,
+    total: usize,
+}
+
+impl Sum {
+    /// Makes a sum of the spaces
+    pub fn new(spaces: Vec<(usize, usize)>, total: usize) -> Sum {
+        return Sum { spaces, total };
+    }
+}
+
+impl Constraint for Sum {
+    fn spaces(&self) -> Vec<(usize, usize)> {
+        return self.spaces.clone();
+    }
+
+    /// Rules out values that are too big for the total, even if every other
+    /// space is its smallest value, or too small, even if every other space
+    /// is its biggest
+    fn eliminations(&self, board: &SudokuBoard) -> Vec<((usize, usize), usize)> {
+        let values: Vec<Vec<usize>> = self
+            .spaces
+            .iter()
+            .map(|&point| board.candidates(point))
+            .collect();
+
+        // Candidates are in order, so the first is the smallest
+        let smallest: usize = values.iter().map(|values| values[0]).sum();
+        let biggest: usize = values.iter().map(|values| values[values.len() - 1]).sum();
+
+        let mut eliminations = Vec::new();
+
+        for (&point, values) in self.spaces.iter().zip(&values) {
+            let others_smallest = smallest - values[0];
+            let others_biggest = biggest - values[values.len() - 1];
+
+            for &value in values {
+                if value + others_smallest > self.total || value + others_biggest < self.total {
+                    eliminations.push((point, value));
+                }
+            }
+        }
+
+        return eliminations;
+    }
+}
+
+/// The constraints of a board, and the regions they make. Shared between
+/// copies of the board, like its geometry
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Constraints {
+    pub(crate) list: Vec<Arc<dyn Constraint>>,
+
+    /// If both diagonals are regions, like in an X sudoku
+    pub(crate) diagonal: bool,
+
+    /// The spaces in each region. The diagonals are first, then the regions
+    /// of each constraint, in the order they were added
+    pub(crate) regions: Vec<Vec<usize>>,
+
+    /// The spaces adjacent to each space, either through a region or by
+    /// being its peer, sorted. Empty if there are no regions
+    peers: Vec<Vec<usize>>,
+}
+
+impl Constraints {
+    /// No constraints. Only made once, and shared
+    pub(crate) fn none() -> Arc<Constraints> {
+        static NONE: OnceLock<Arc<Constraints>> = OnceLock::new();
+
+        return Arc::clone(NONE.get_or_init(|| Arc::new(Constraints::default())));
+    }
+
+    /// Returns if there are no rules on top of the rows, columns, and
+    /// boxes
+    pub(crate) fn is_empty(&self) -> bool {
+        return self.list.is_empty() && !self.diagonal;
+    }
+
+    /// Adds a constraint, and its regions
+    ///
+    /// Returns `OffBoard` if the constraint or one of its regions has a
+    /// space that isn't on the board
+    pub(crate) fn add(
+        &mut self,
+        constraint: Arc<dyn Constraint>,
+        geometry: &Geometry,
+    ) -> Result<(), SudokuError> {
+        self.list.push(constraint);
+
+        if let Result::Err(error) = self.build(geometry) {
+            self.list.pop();
+            return Result::Err(error);
+        }
+
+        return Result::Ok(());
+    }
+
+    /// Makes both diagonals regions, or stops them being regions
+    pub(crate) fn set_diagonal(&mut self, diagonal: bool, geometry: &Geometry) {
+        self.diagonal = diagonal;
+
+        self.build(geometry)
+            .expect("Regions are checked when their constraint is added");
+    }
+
+    /// Works out the regions, and the peers they make, from the diagonals
+    /// and each constraint. Nothing is changed if a constraint or region
+    /// has a space that isn't on the board
+    fn build(&mut self, geometry: &Geometry) -> Result<(), SudokuError> {
+        let size = geometry.size();
+        let mut regions = Vec::new();
+
+        for constraint in &self.list {
+            for point in constraint.spaces() {
+                if !geometry.contains(point) {
+                    return Result::Err(SudokuError::OffBoard { point });
+                }
+            }
+        }
+
+        if self.diagonal {
+            regions.push((0..size).map(|i| geometry.index_of((i, i))).collect());
+            regions.push(
+                (0..size)
+                    .map(|i| geometry.index_of((i, size - 1 - i)))
+                    .collect(),
+            );
+        }
+
+        for constraint in &self.list {
+            for region in constraint.regions(geometry) {
+                let mut indexes = Vec::with_capacity(region.len());
+
+                for point in region {
+                    if !geometry.contains(point) {
+                        return Result::Err(SudokuError::OffBoard { point });
+                    }
+
+                    indexes.push(geometry.index_of(point));
+                }
+
+                regions.push(indexes);
+            }
+        }
+
+        let mut peers = Vec::new();
+
+        if !regions.is_empty() {
+            peers = (0..geometry.spaces())
+                .map(|index| geometry.peers(index).to_vec())
+                .collect();
+        }
+
+        for region in &regions {
+            for &index in region {
+                for &other in region {
+                    if other == index {
+                        continue;
+                    }
+
+                    if let Result::Err(position) = peers[index].binary_search(&other) {
+                        peers[index].insert(position, other);
+                    }
+                }
+            }
+        }
+
+        self.regions = regions;
+        self.peers = peers;
+
+        return Result::Ok(());
+    }
+
+    /// Returns the spaces adjacent to a space, either through a region or
+    /// by being its peer in `geometry`, sorted
+    ///
+    /// Without regions, these are just the peers from `geometry`, so there's
+    /// only one list to go through either way
+    pub(crate) fn peers<'a>(&'a self, geometry: &'a Geometry, index: usize) -> &'a [usize] {
+        match self.peers.get(index) {
+            Some(peers) => return peers,
+            None => return geometry.peers(index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Relation, SudokuBoard, SudokuError, Sum};
+
+    fn empty_board() -> SudokuBoard {
+        return "0".repeat(81).parse().unwrap();
+    }
+
+    #[test]
+    fn relation_off_the_board() {
+        let mut board = empty_board();
+
+        assert!(matches!(
+            board.add_constraint(Relation::less_than((9, 0), (0, 0))),
+            Result::Err(SudokuError::OffBoard { point: (9, 0) })
+        ));
+        assert_eq!(board.constraints().count(), 0);
+        assert!(board.solve());
+    }
+
+    #[test]
+    fn sum_off_the_board() {
+        let mut board = empty_board();
+
+        // (0, 12) would wrap round to (1, 3)
+        assert!(matches!(
+            board.add_constraint(Sum::new(vec![(0, 12)], 3)),
+            Result::Err(SudokuError::OffBoard { point: (0, 12) })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 1, column 10 isn't on the board")]
+    fn value_off_the_board() {
+        empty_board().value((0, 9));
+    }
+}
@@ -0,0 +1,503 @@
+//! Making new sudoku's, with one solution each
+//!
+//! A random solved board is made first, by solving a blank board with
+//! random guesses. Then givens are taken away a few at a time, in a random
+//! order, putting them back if the sudoku would have more than one solution.
+//! If the sudoku doesn't fit the generator's settings, it starts again
+
+use std::{ops::RangeInclusive, sync::Arc};
+
+use crate::{Geometry, Level, SudokuBoard, SudokuError, SudokuValue, TechniqueSet};
+
+/// A sudoku, and its solution. Made by `Generator::generate`
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// The givens, with every other space empty
+    pub board: SudokuBoard,
+
+    /// The board, solved
+    pub solution: SudokuBoard,
+}
+
+/// Which spaces have to be given together, so the givens make a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Any givens
+    None,
+
+    /// The givens look the same turned upside down
+    Rotational,
+
+    /// The givens are mirrored across the diagonal from the top left to the
+    /// bottom right
+    Diagonal,
+
+    /// The givens are mirrored from left to right
+    Mirror,
+
+    /// The givens look the same turned a quarter turn, or mirrored across
+    /// either diagonal, or from left to right, or top to bottom
+    Dihedral,
+}
+
+/// Makes sudoku's with one solution. The same seed and settings always make
+/// the same sudoku's, in the same order
+///
+/// ```
+/// use rsudoku::{Generator, Level, Symmetry};
+///
+/// let puzzle = Generator::new(42).generate().unwrap();
+///
+/// assert!(puzzle.board.has_unique_solution());
+/// assert!(puzzle.solution.is_solved());
+///
+/// let again = Generator::new(42).generate().unwrap();
+/// assert_eq!(puzzle.board.to_string(), again.board.to_string());
+///
+/// let mut generator = Generator::new(7);
+/// generator.set_symmetry(Symmetry::Rotational);
+/// generator.set_levels(Level::Hard..=Level::Hard);
+///
+/// let puzzle = generator.generate().unwrap();
+/// assert_eq!(puzzle.board.rate().unwrap().level, Level::Hard);
+/// ```
+///
+/// Other shapes of board can be made too
+///
+/// ```
+/// use rsudoku::{Generator, Geometry};
+///
+/// let mut generator = Generator::new(1);
+/// generator.set_geometry(Geometry::new(3, 2).unwrap());
+///
+/// let puzzle = generator.generate().unwrap();
+/// assert_eq!(puzzle.board.geometry().size(), 6);
+/// assert!(puzzle.board.has_unique_solution());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Generator {
+    rng: Rng,
+    geometry: Arc<Geometry>,
+    symmetry: Symmetry,
+    target_givens: usize,
+    max_givens: usize,
+    levels: RangeInclusive<Level>,
+    attempts: usize,
+}
+
+impl Generator {
+    /// Creates a generator from a seed. It makes 9x9 sudoku's, with no
+    /// symmetry, no limit on the givens or difficulty, and makes 100
+    /// attempts
+    pub fn new(seed: u64) -> Generator {
+        return Generator {
+            rng: Rng::new(seed),
+            geometry: Geometry::standard(),
+            symmetry: Symmetry::None,
+            target_givens: 0,
+            max_givens: usize::MAX,
+            levels: Level::Easy..=Level::Extreme,
+            attempts: 100,
+        };
+    }
+
+    /// Sets the shape of the sudoku's
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        self.geometry = Arc::new(geometry);
+    }
+
+    /// Sets the pattern the givens have to make
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) {
+        self.symmetry = symmetry;
+    }
+
+    /// Sets how many givens to stop at. Givens are taken away until there
+    /// are this many or fewer, or no more can be taken away. By default,
+    /// it's 0, so as many as possible are taken away
+    ///
+    /// With symmetry, givens are taken away a few at a time, so there can
+    /// be a few less than the target
+    pub fn set_target_givens(&mut self, target_givens: usize) {
+        self.target_givens = target_givens;
+    }
+
+    /// Sets the most givens a sudoku can have. Sudoku's with more are
+    /// thrown away, and another attempt is made
+    pub fn set_max_givens(&mut self, max_givens: usize) {
+        self.max_givens = max_givens;
+    }
+
+    /// Sets the levels the sudoku's have to be, from `SudokuBoard::rate`.
+    /// Sudoku's of other levels are thrown away, and another attempt is
+    /// made
+    pub fn set_levels(&mut self, levels: RangeInclusive<Level>) {
+        self.levels = levels;
+    }
+
+    /// Sets how many sudoku's can be made and thrown away before giving up
+    pub fn set_attempts(&mut self, attempts: usize) {
+        self.attempts = attempts;
+    }
+
+    /// Makes a new sudoku with one solution, that fits the settings
+    ///
+    /// Returns `GenerateFailed` if none of the attempts fit, with how many
+    /// failed for each reason
+    pub fn generate(&mut self) -> Result<Puzzle, SudokuError> {
+        let mut too_many_givens = 0;
+        let mut wrong_level = 0;
+
+        for _ in 0..self.attempts {
+            let puzzle = self.attempt();
+            let givens = grid_of(&puzzle.board)
+                .iter()
+                .filter(|&&value| value != 0)
+                .count();
+
+            if givens > self.max_givens {
+                too_many_givens += 1;
+                continue;
+            }
+
+            if self.levels != (Level::Easy..=Level::Extreme) {
+                // The sudoku has one solution, so every technique can be
+                // used to rate it
+                let mut rated_board = puzzle.board.clone();
+                rated_board.set_assume_unique_solution(true);
+
+                let level = rated_board.rate()?.level;
+                if !self.levels.contains(&level) {
+                    wrong_level += 1;
+                    continue;
+                }
+            }
+
+            return Result::Ok(puzzle);
+        }
+
+        return Result::Err(SudokuError::GenerateFailed {
+            attempts: self.attempts,
+            too_many_givens,
+            wrong_level,
+        });
+    }
+
+    /// Makes a sudoku with one solution, ignoring the max givens and levels
+    fn attempt(&mut self) -> Puzzle {
+        let solution = self.random_solution();
+        let mut grid = grid_of(&solution);
+        let mut givens = grid.len();
+
+        let mut tried = vec![false; grid.len()];
+
+        let mut order: Vec<usize> = (0..grid.len()).collect();
+        self.rng.shuffle(&mut order);
+
+        for index in order {
+            if givens <= self.target_givens {
+                break;
+            }
+
+            // Spaces that were tried already, as part of another space's
+            // group, are skipped
+            if tried[index] {
+                continue;
+            }
+
+            let group = self.symmetry.group(index, self.geometry.size());
+            for &space in &group {
+                tried[space] = true;
+            }
+
+            let mut removed = grid.clone();
+            for &space in &group {
+                removed[space] = 0;
+            }
+
+            if board_of(&removed, &self.geometry).has_unique_solution() {
+                grid = removed;
+                givens -= group.len();
+            }
+        }
+
+        return Puzzle {
+            board: board_of(&grid, &self.geometry),
+            solution,
+        };
+    }
+
+    /// Returns a random solved board
+    fn random_solution(&mut self) -> SudokuBoard {
+        let mut board = board_of(&vec![0; self.geometry.spaces()], &self.geometry);
+        board.techniques = TechniqueSet::empty();
+
+        // A blank board always has a solution
+        solve_randomly(&mut board, &mut self.rng).unwrap();
+
+        return board;
+    }
+}
+
+impl Symmetry {
+    /// Returns the spaces that have to be given along with a space,
+    /// including itself, on a board `size` spaces wide
+    fn group(self, index: usize, size: usize) -> Vec<usize> {
+        let (row, column) = (index / size, index % size);
+        let (flipped_row, flipped_column) = (size - 1 - row, size - 1 - column);
+
+        let points = match self {
+            Symmetry::None => vec![(row, column)],
+            Symmetry::Rotational => vec![(row, column), (flipped_row, flipped_column)],
+            Symmetry::Diagonal => vec![(row, column), (column, row)],
+            Symmetry::Mirror => vec![(row, column), (row, flipped_column)],
+            Symmetry::Dihedral => vec![
+                (row, column),
+                (column, flipped_row),
+                (flipped_row, flipped_column),
+                (flipped_column, row),
+                (row, flipped_column),
+                (flipped_row, column),
+                (column, row),
+                (flipped_column, flipped_row),
+            ],
+        };
+
+        let mut group: Vec<usize> = points
+            .into_iter()
+            .map(|(row, column)| row * size + column)
+            .collect();
+        group.sort_unstable();
+        group.dedup();
+
+        return group;
+    }
+}
+
+/// Solves a board like `SudokuBoard::try_solve`, but guesses a random value
+/// for the most impactful guess's space
+fn solve_randomly(board: &mut SudokuBoard, rng: &mut Rng) -> Result<(), SudokuError> {
+    if !board.initialized {
+        board.initial_check()?;
+    }
+
+    while !board.narrow_full()? {
+        let (index, _) = board.most_impactful_guess();
+        let values: Vec<usize> = board.possible_values(index).iter().collect();
+        let guess_value = values[rng.below(values.len())];
+
+        let mut guess_board = board.clone();
+
+        if guess_board.fill_space(index, guess_value).is_ok()
+            && solve_randomly(&mut guess_board, rng).is_ok()
+        {
+            *board = guess_board;
+            return Result::Ok(());
+        }
+
+        board.remove_guess(index, guess_value)?;
+
+        if board.is_solved() {
+            return Result::Ok(());
+        }
+    }
+
+    return Result::Ok(());
+}
+
+/// Returns the value of each space of a board, or 0 if it isn't known
+pub(crate) fn grid_of(board: &SudokuBoard) -> Vec<u8> {
+    let mut grid = vec![0; board.geometry.spaces()];
+
+    for (index, space) in grid.iter_mut().enumerate() {
+        if let SudokuValue::Known(value) = board.get_space(index) {
+            *space = *value;
+        }
+    }
+
+    return grid;
+}
+
+/// Makes a board from the value of each space, or 0 for empty spaces
+pub(crate) fn board_of(grid: &[u8], geometry: &Arc<Geometry>) -> SudokuBoard {
+    return SudokuBoard::from_bytes(grid, Arc::clone(geometry)).unwrap();
+}
+
+/// A small SplitMix64 random number generator, so generating doesn't need
+/// any dependencies
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        return Rng { state: seed };
+    }
+
+    /// Returns the next random number
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        return z ^ (z >> 31);
+    }
+
+    /// Returns a random number from 0 up to, but not including, `limit`
+    pub(crate) fn below(&mut self, limit: usize) -> usize {
+        return (self.next_u64() % limit as u64) as usize;
+    }
+
+    /// Shuffles a slice into a random order
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for last in (1..items.len()).rev() {
+            items.swap(last, self.below(last + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grid_of, Generator, Symmetry};
+    use crate::{Geometry, Level, SudokuError};
+
+    /// Returns how many spaces of the board are given
+    fn givens(grid: &[u8]) -> usize {
+        return grid.iter().filter(|&&value| value != 0).count();
+    }
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        for seed in 0..3 {
+            let first = Generator::new(seed).generate().unwrap();
+            let second = Generator::new(seed).generate().unwrap();
+
+            assert_eq!(grid_of(&first.board), grid_of(&second.board));
+            assert_eq!(grid_of(&first.solution), grid_of(&second.solution));
+        }
+
+        // And the generator carries on the same way too
+        let mut first = Generator::new(5);
+        let mut second = Generator::new(5);
+        for _ in 0..2 {
+            assert_eq!(
+                grid_of(&first.generate().unwrap().board),
+                grid_of(&second.generate().unwrap().board)
+            );
+        }
+    }
+
+    #[test]
+    fn different_seeds_different_puzzles() {
+        let first = Generator::new(1).generate().unwrap();
+        let second = Generator::new(2).generate().unwrap();
+
+        assert_ne!(grid_of(&first.board), grid_of(&second.board));
+    }
+
+    #[test]
+    fn puzzles_match_their_solutions() {
+        let mut generator = Generator::new(3);
+        generator.set_geometry(Geometry::new(3, 2).unwrap());
+
+        let puzzle = generator.generate().unwrap();
+        assert!(puzzle.board.has_unique_solution());
+        assert!(puzzle.solution.is_solved());
+
+        let mut solved = puzzle.board.clone();
+        assert!(solved.solve());
+        assert_eq!(grid_of(&solved), grid_of(&puzzle.solution));
+
+        for (given, value) in grid_of(&puzzle.board)
+            .into_iter()
+            .zip(grid_of(&puzzle.solution))
+        {
+            assert!(given == 0 || given == value);
+        }
+    }
+
+    #[test]
+    fn symmetry_holds() {
+        for symmetry in [
+            Symmetry::None,
+            Symmetry::Rotational,
+            Symmetry::Diagonal,
+            Symmetry::Mirror,
+            Symmetry::Dihedral,
+        ] {
+            let mut generator = Generator::new(11);
+            generator.set_symmetry(symmetry);
+
+            let puzzle = generator.generate().unwrap();
+            assert!(puzzle.board.has_unique_solution());
+
+            // Every space in a group is given, or none of them are
+            let grid = grid_of(&puzzle.board);
+            for index in 0..grid.len() {
+                for space in symmetry.group(index, 9) {
+                    assert_eq!(grid[space] != 0, grid[index] != 0, "{:?}", symmetry);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn target_givens() {
+        let mut generator = Generator::new(4);
+        generator.set_target_givens(40);
+        assert_eq!(givens(&grid_of(&generator.generate().unwrap().board)), 40);
+
+        // Givens are taken away up to 8 at a time, so it can go a little under
+        let mut generator = Generator::new(4);
+        generator.set_symmetry(Symmetry::Dihedral);
+        generator.set_target_givens(40);
+
+        let count = givens(&grid_of(&generator.generate().unwrap().board));
+        assert!((33..=40).contains(&count), "{}", count);
+    }
+
+    #[test]
+    fn max_givens() {
+        let mut generator = Generator::new(8);
+        generator.set_max_givens(25);
+
+        for _ in 0..3 {
+            let puzzle = generator.generate().unwrap();
+            assert!(givens(&grid_of(&puzzle.board)) <= 25);
+            assert!(puzzle.board.has_unique_solution());
+        }
+    }
+
+    #[test]
+    fn levels() {
+        let mut generator = Generator::new(9);
+        generator.set_levels(Level::Medium..=Level::Hard);
+
+        for _ in 0..3 {
+            let mut board = generator.generate().unwrap().board;
+            board.set_assume_unique_solution(true);
+
+            let level = board.rate().unwrap().level;
+            assert!((Level::Medium..=Level::Hard).contains(&level), "{}", level);
+        }
+    }
+
+    #[test]
+    fn running_out_of_attempts() {
+        // No 9x9 sudoku has one solution with 16 givens or less
+        let mut generator = Generator::new(6);
+        generator.set_max_givens(16);
+        generator.set_attempts(3);
+
+        assert!(matches!(
+            generator.generate(),
+            Result::Err(SudokuError::GenerateFailed {
+                attempts: 3,
+                too_many_givens: 3,
+                wrong_level: 0,
+            })
+        ));
+    }
+}
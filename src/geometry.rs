@@ -0,0 +1,290 @@
+//! The shape of a board, and the rows, columns, and boxes that come from it
+
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{SudokuError, Unit};
+
+/// The shape of a board. Boxes are `box_width` spaces wide and `box_height`
+/// tall, and the board is `size` by `size` spaces, where `size` is
+/// `box_width * box_height`. Values go from 1 to `size`
+///
+/// The rows, columns, and boxes, and the spaces adjacent to each space, are
+/// worked out once, when the geometry is made
+///
+/// ```
+/// use rsudoku::Geometry;
+///
+/// // 6x6, with boxes 3 wide and 2 tall
+/// let geometry = Geometry::new(3, 2).unwrap();
+///
+/// assert_eq!(geometry.size(), 6);
+/// assert_eq!(geometry.spaces(), 36);
+/// assert_eq!(geometry.peers(0).len(), 12);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Geometry {
+    box_width: usize,
+    box_height: usize,
+    size: usize,
+
+    /// The spaces in each unit. Rows are units 0 to `size - 1`, then the
+    /// columns, then the boxes. Spaces are listed left to right, then top
+    /// to bottom
+    units: Vec<Vec<usize>>,
+
+    /// The indexes in `units` of the row, column, and box of each space
+    units_of_cell: Vec<[usize; 3]>,
+
+    /// The spaces adjacent to each space, sorted
+    peers: Vec<Vec<usize>>,
+}
+
+impl Geometry {
+    /// The most values a board can have, since possible values are stored
+    /// as a 32 bit mask, without bit 0
+    pub const MAX_SIZE: usize = 31;
+
+    /// Makes the geometry for boxes `box_width` wide and `box_height` tall
+    ///
+    /// Returns `InvalidGeometry` if either is 0, or the board would have
+    /// more than `MAX_SIZE` values
+    pub fn new(box_width: usize, box_height: usize) -> Result<Geometry, SudokuError> {
+        let invalid = SudokuError::InvalidGeometry {
+            box_width,
+            box_height,
+        };
+
+        let size = match box_width.checked_mul(box_height) {
+            Some(size) if size > 0 && size <= Geometry::MAX_SIZE => size,
+            _ => return Result::Err(invalid),
+        };
+
+        let mut units = vec![Vec::with_capacity(size); size * 3];
+        let mut units_of_cell = Vec::with_capacity(size * size);
+
+        for index in 0..size * size {
+            let (row, column) = (index / size, index % size);
+            let box_index = row / box_height * box_height + column / box_width;
+
+            let cell_units = [row, size + column, size * 2 + box_index];
+            for unit in cell_units {
+                units[unit].push(index);
+            }
+
+            units_of_cell.push(cell_units);
+        }
+
+        let peers = (0..size * size)
+            .map(|index| {
+                (0..size * size)
+                    .filter(|&other| other != index)
+                    .filter(|&other| {
+                        (0..3).any(|kind| units_of_cell[index][kind] == units_of_cell[other][kind])
+                    })
+                    .collect()
+            })
+            .collect();
+
+        return Result::Ok(Geometry {
+            box_width,
+            box_height,
+            size,
+            units,
+            units_of_cell,
+            peers,
+        });
+    }
+
+    /// The usual 9x9 board, with 3x3 boxes. It's only made once, and
+    /// shared
+    pub(crate) fn standard() -> Arc<Geometry> {
+        static STANDARD: OnceLock<Arc<Geometry>> = OnceLock::new();
+
+        return Arc::clone(STANDARD.get_or_init(|| Arc::new(Geometry::new(3, 3).unwrap())));
+    }
+
+    /// Returns how wide each box is
+    pub fn box_width(&self) -> usize {
+        return self.box_width;
+    }
+
+    /// Returns how tall each box is
+    pub fn box_height(&self) -> usize {
+        return self.box_height;
+    }
+
+    /// Returns the number of values, and the width and height of the board
+    pub fn size(&self) -> usize {
+        return self.size;
+    }
+
+    /// Returns the number of spaces on the board
+    pub fn spaces(&self) -> usize {
+        return self.size * self.size;
+    }
+
+    /// Returns the spaces in every row, column, and box. Spaces are indexed
+    /// row by row, so the space at (row, column) is `row * size + column`
+    pub fn units(&self) -> &[Vec<usize>] {
+        return &self.units;
+    }
+
+    /// Returns the indexes in `units` of the row, column, and box of a
+    /// space
+    pub fn units_of_cell(&self, index: usize) -> [usize; 3] {
+        return self.units_of_cell[index];
+    }
+
+    /// Returns the spaces adjacent to a space, which are the ones in the
+    /// same row, column, or box, sorted
+    pub fn peers(&self, index: usize) -> &[usize] {
+        return &self.peers[index];
+    }
+
+    /// Returns the unit at an index of `units`. Indexes after the boxes are
+    /// the regions of the board's constraints
+    pub fn unit(&self, index: usize) -> Unit {
+        if index < self.size {
+            return Unit::Row(index);
+        } else if index < self.size * 2 {
+            return Unit::Column(index - self.size);
+        } else if index < self.size * 3 {
+            return Unit::Box(index - self.size * 2);
+        } else {
+            return Unit::Region(index - self.size * 3);
+        }
+    }
+
+    /// Returns the index of a unit in `units`, or after them for regions
+    pub fn unit_index(&self, unit: Unit) -> usize {
+        match unit {
+            Unit::Row(index) => return index,
+            Unit::Column(index) => return index + self.size,
+            Unit::Box(index) => return index + self.size * 2,
+            Unit::Region(index) => return index + self.size * 3,
+        }
+    }
+
+    /// Returns the (row, column) of a space
+    pub fn point_of(&self, index: usize) -> (usize, usize) {
+        return (index / self.size, index % self.size);
+    }
+
+    /// Returns if there's a space at (row, column)
+    pub fn contains(&self, (row, column): (usize, usize)) -> bool {
+        return row < self.size && column < self.size;
+    }
+
+    /// Returns the index of the space at (row, column), which has to be on
+    /// the board
+    pub fn index_of(&self, (row, column): (usize, usize)) -> usize {
+        return row * self.size + column;
+    }
+}
+
+impl fmt::Debug for Geometry {
+    /// Only prints the box size, since the tables are large
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f
+            .debug_struct("Geometry")
+            .field("box_width", &self.box_width)
+            .field("box_height", &self.box_height)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, SudokuBoard, SudokuError, Unit};
+
+    #[test]
+    fn invalid_shapes() {
+        for (box_width, box_height) in [(0, 3), (3, 0), (0, 0), (4, 8), (32, 1), (usize::MAX, 2)] {
+            assert!(
+                matches!(
+                    Geometry::new(box_width, box_height),
+                    Result::Err(SudokuError::InvalidGeometry { box_width: w, box_height: h })
+                        if w == box_width && h == box_height
+                ),
+                "{}x{}",
+                box_width,
+                box_height
+            );
+        }
+
+        assert!(Geometry::new(31, 1).is_ok());
+    }
+
+    /// Checks every unit has each space once, and each space is in one
+    /// row, column, and box, with peers from just those
+    fn check_units(geometry: &Geometry) {
+        let size = geometry.size();
+        assert_eq!(geometry.spaces(), size * size);
+        assert_eq!(geometry.units().len(), size * 3);
+
+        for unit in geometry.units() {
+            let mut spaces = unit.clone();
+            spaces.sort();
+            spaces.dedup();
+            assert_eq!(spaces.len(), size);
+        }
+
+        let peer_count = 3 * (size - 1) - (geometry.box_width() - 1) - (geometry.box_height() - 1);
+
+        for index in 0..geometry.spaces() {
+            for unit in geometry.units_of_cell(index) {
+                assert!(geometry.units()[unit].contains(&index));
+            }
+
+            assert_eq!(geometry.peers(index).len(), peer_count);
+            assert_eq!(geometry.index_of(geometry.point_of(index)), index);
+        }
+    }
+
+    #[test]
+    fn four_by_four() {
+        let geometry = Geometry::new(2, 2).unwrap();
+        check_units(&geometry);
+
+        assert_eq!(geometry.size(), 4);
+        assert_eq!(
+            geometry.units()[geometry.unit_index(Unit::Box(1))],
+            vec![2, 3, 6, 7]
+        );
+        assert_eq!(geometry.peers(0), &[1, 2, 3, 4, 5, 8, 12]);
+    }
+
+    #[test]
+    fn six_by_six() {
+        let geometry = Geometry::new(3, 2).unwrap();
+        check_units(&geometry);
+
+        // Boxes are 3 wide and 2 tall, so there are 2 across and 3 down
+        assert_eq!(
+            geometry.units()[geometry.unit_index(Unit::Box(1))],
+            vec![3, 4, 5, 9, 10, 11]
+        );
+        assert_eq!(geometry.unit(geometry.units_of_cell(14)[2]), Unit::Box(2));
+        assert!(geometry.contains((5, 5)));
+        assert!(!geometry.contains((0, 6)));
+
+        let mut board = SudokuBoard::from_grid(&[0; 36], geometry).unwrap();
+        assert!(board.solve());
+    }
+
+    #[test]
+    fn sixteen_by_sixteen() {
+        let geometry = Geometry::new(4, 4).unwrap();
+        check_units(&geometry);
+
+        assert_eq!(geometry.spaces(), 256);
+        assert_eq!(geometry.units_of_cell(255), [15, 31, 47]);
+        assert_eq!(geometry.point_of(17), (1, 1));
+
+        let mut board = SudokuBoard::from_grid(&[0; 256], geometry).unwrap();
+        assert!(board.solve());
+    }
+}
@@ -0,0 +1,273 @@
+//! Finding the easiest next step, for someone solving a sudoku themselves
+
+use std::fmt;
+
+use crate::{techniques, Pattern, Step, StepKind, SudokuBoard, SudokuValue};
+
+/// The easiest step that can be taken next on a board. Made by
+/// `SudokuBoard::hint`
+///
+/// ```
+/// use rsudoku::{StepKind, SudokuBoard};
+///
+/// let board = SudokuBoard::from_file("boards/easy").unwrap();
+///
+/// let hint = board.hint().unwrap();
+/// assert!(matches!(hint.step.kind, StepKind::NakedSingle { .. }));
+/// println!("{}", hint);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    /// What the step is, and the values it fills in or removes. It's never
+    /// a guess or backtrack
+    pub step: Step,
+
+    /// The pattern the technique found. Singles don't have one, their space
+    /// is in `step.kind`
+    pub pattern: Option<Pattern>,
+}
+
+impl SudokuBoard {
+    /// Returns the easiest step that can be taken next, without taking it.
+    /// Naked singles are looked for first, after removing what the
+    /// constraints rule out, then hidden singles, then the board's
+    /// techniques, easiest first
+    ///
+    /// Returns None if nothing can be found without guessing, or if the
+    /// board is solved or unsolvable
+    ///
+    /// Once a board has been narrowed, any space left with one possible
+    /// value is already filled in, so only boards that haven't been narrowed
+    /// yet, like ones that were just read in, have naked singles
+    pub fn hint(&self) -> Option<Hint> {
+        let mut board = self.clone();
+
+        if !board.initialized {
+            board.check_duplicate_givens().ok()?;
+
+            if let Some(hint) = board.find_naked_single() {
+                return Some(hint);
+            }
+
+            // There are no naked singles, so this won't fill in any spaces
+            board.initial_check().ok()?;
+        }
+
+        if let Some((unit, index, value)) = board.find_hidden_single().ok()? {
+            return Some(Hint {
+                step: Step {
+                    kind: StepKind::HiddenSingle {
+                        unit: board.geometry.unit(unit),
+                        space: board.geometry.point_of(index),
+                    },
+                    placements: vec![(board.geometry.point_of(index), value)],
+                    eliminations: Vec::new(),
+                },
+                pattern: None,
+            });
+        }
+
+        for technique in board.techniques.iter() {
+            if let Some(deduction) = techniques::find(&board, technique) {
+                return Some(Hint {
+                    step: Step {
+                        kind: StepKind::Technique(technique),
+                        placements: deduction.placements,
+                        eliminations: deduction.eliminations,
+                    },
+                    pattern: Some(deduction.pattern),
+                });
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns the first unknown space that only has one value left once
+    /// the known values adjacent to it, and the values the constraints rule
+    /// out, are removed. For boards that haven't had their initial check
+    ///
+    /// Returns None if a space has no values left, as the board is
+    /// unsolvable
+    fn find_naked_single(&self) -> Option<Hint> {
+        let mut board = self.clone();
+
+        for index in 0..self.geometry.spaces() {
+            if let SudokuValue::Unknown(mut possible_values) = *self.get_space(index) {
+                for &adjacent_index in self.constraints.peers(&self.geometry, index) {
+                    if let SudokuValue::Known(value) = self.get_space(adjacent_index) {
+                        possible_values.remove(*value as usize);
+                    }
+                }
+
+                if possible_values.len() == 0 {
+                    return None;
+                }
+
+                // Left unknown even with one value, so the constraints see
+                // the same board a player would
+                *board.get_space_mut(index) = SudokuValue::Unknown(possible_values);
+            }
+        }
+
+        for (index, value) in board.constraint_eliminations().ok()? {
+            match board.get_space_mut(index) {
+                SudokuValue::Known(_) => return None,
+                SudokuValue::Unknown(possible_values) => {
+                    possible_values.remove(value);
+                }
+            }
+        }
+
+        for index in 0..self.geometry.spaces() {
+            let possible_values = match board.get_space(index) {
+                SudokuValue::Known(_) => continue,
+                SudokuValue::Unknown(possible_values) => *possible_values,
+            };
+
+            match possible_values.only_value() {
+                Some(value) => {
+                    return Some(Hint {
+                        step: Step {
+                            kind: StepKind::NakedSingle {
+                                space: self.geometry.point_of(index),
+                            },
+                            placements: vec![(self.geometry.point_of(index), value)],
+                            eliminations: Vec::new(),
+                        },
+                        pattern: None,
+                    });
+                }
+                None if possible_values.len() == 0 => return None,
+                None => {}
+            }
+        }
+
+        return None;
+    }
+}
+
+impl fmt::Display for Hint {
+    /// Prints the step, like a step in a trace
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Relation, StepKind, SudokuBoard, Trace};
+
+    /// Takes the hints one at a time, until one matches `matches`, and
+    /// returns the board it was found on, and the hint
+    fn board_with_hint(
+        board: &SudokuBoard,
+        matches: fn(&StepKind) -> bool,
+    ) -> (SudokuBoard, super::Hint) {
+        let mut board = board.clone();
+
+        loop {
+            let hint = board.hint().expect("Ran out of hints");
+            if matches(&hint.step.kind) {
+                return (board, hint);
+            }
+
+            let trace = Trace {
+                steps: vec![hint.step],
+            };
+            board.replay(&trace).unwrap();
+        }
+    }
+
+    #[test]
+    fn naked_single() {
+        let board = SudokuBoard::from_file("boards/easy").unwrap();
+        let hint = board.hint().unwrap();
+
+        let StepKind::NakedSingle { space } = hint.step.kind else {
+            panic!("Expected a naked single, got {}", hint);
+        };
+        assert_eq!(hint.pattern, None);
+
+        let mut solved = board.clone();
+        assert!(solved.solve());
+        assert_eq!(
+            hint.step.placements,
+            vec![(space, solved.value(space).unwrap())]
+        );
+    }
+
+    #[test]
+    fn hidden_single() {
+        let board = SudokuBoard::from_file("boards/medium").unwrap();
+        let (_, hint) =
+            board_with_hint(&board, |kind| matches!(kind, StepKind::HiddenSingle { .. }));
+
+        let mut solved = board.clone();
+        assert!(solved.solve());
+
+        for &(space, value) in &hint.step.placements {
+            assert_eq!(solved.value(space), Some(value));
+        }
+    }
+
+    #[test]
+    fn technique() {
+        let board = SudokuBoard::from_file("boards/hard").unwrap();
+        let (hinted, hint) = board_with_hint(&board, |kind| matches!(kind, StepKind::Technique(_)));
+
+        let StepKind::Technique(technique) = hint.step.kind else {
+            unreachable!();
+        };
+        assert!(board.techniques().contains(technique));
+
+        // It's what the technique finds, and no easier one finds anything
+        let deduction = hinted.find_deduction(technique).unwrap();
+        assert_eq!(hint.step.eliminations, deduction.eliminations);
+        assert_eq!(hint.pattern, Some(deduction.pattern));
+
+        for easier in board
+            .techniques()
+            .iter()
+            .take_while(|&easier| easier < technique)
+        {
+            assert_eq!(hinted.find_deduction(easier), None, "{}", easier);
+        }
+    }
+
+    #[test]
+    fn nothing_but_guessing() {
+        let blank: SudokuBoard = "0".repeat(81).parse().unwrap();
+        assert_eq!(blank.hint(), None);
+
+        let mut solved = SudokuBoard::from_file("boards/easy").unwrap();
+        assert!(solved.solve());
+        assert_eq!(solved.hint(), None);
+    }
+
+    #[test]
+    fn constraints_come_before_naked_singles() {
+        // Row 1 has 1 to 7, so the last two spaces are 8 and 9, and the
+        // relation decides which
+        let mut board: SudokuBoard = format!("123456700{}", "0".repeat(72)).parse().unwrap();
+        board
+            .add_constraint(Relation::less_than((0, 8), (0, 7)))
+            .unwrap();
+
+        let hint = board.hint().unwrap();
+        assert_eq!(hint.step.kind, StepKind::NakedSingle { space: (0, 7) });
+        assert_eq!(hint.step.placements, vec![((0, 7), 9)]);
+    }
+
+    #[test]
+    fn no_naked_single_the_constraints_rule_out() {
+        // Row 1 leaves 9 for its last space, but it has to be less than the
+        // space below it
+        let mut board: SudokuBoard = format!("123456780{}", "0".repeat(72)).parse().unwrap();
+        board
+            .add_constraint(Relation::less_than((0, 8), (1, 8)))
+            .unwrap();
+
+        assert_eq!(board.hint(), None);
+    }
+}
@@ -1,58 +1,528 @@
 // Clippy configurations
 #![allow(clippy::needless_return)]
 
-use std::{cmp::Ordering, collections::VecDeque, fmt, fs, io};
+use std::{cmp::Ordering, error, fmt, fs, io, ops, path::Path, str::FromStr, sync::Arc};
+
+use constraint::Constraints;
+
+mod constraint;
+mod generator;
+mod geometry;
+mod hint;
+mod minimize;
+mod rating;
+mod symbols;
+mod techniques;
+mod trace;
+
+#[cfg(test)]
+mod tests;
+
+pub use constraint::{Constraint, Region, Relation, Sum};
+pub use generator::{Generator, Puzzle, Symmetry};
+pub use geometry::Geometry;
+pub use hint::Hint;
+pub use minimize::RemovalOrder;
+pub use rating::{HardestStep, Level, Rating};
+pub use symbols::Symbols;
+pub use techniques::{Chain, ChainNode, Deduction, Link, Pattern, Technique, TechniqueSet};
+pub use trace::{Step, StepKind, Trace};
+
+/// A row, column, or box of the board, or a region from a constraint.
+/// Indexes start at 0, boxes are numbered left to right, then top to
+/// bottom, and regions in the order they were added. See `Geometry::unit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+    Region(usize),
+}
+
+impl fmt::Display for Unit {
+    /// Print the unit, counting from 1, like "row 3"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row(index) => write!(f, "row {}", index + 1),
+            Self::Column(index) => write!(f, "column {}", index + 1),
+            Self::Box(index) => write!(f, "box {}", index + 1),
+            Self::Region(index) => write!(f, "region {}", index + 1),
+        }
+    }
+}
+
+impl Unit {
+    /// Returns the unit at an index of `UNITS`, on a 9x9 board. Indexes
+    /// after the boxes are regions. For other sizes, see `Geometry::unit`
+    pub fn from_index(index: usize) -> Unit {
+        match index {
+            0..=8 => Unit::Row(index),
+            9..=17 => Unit::Column(index - 9),
+            18..=26 => Unit::Box(index - 18),
+            _ => Unit::Region(index - 27),
+        }
+    }
+
+    /// Returns the index of the unit in `UNITS`, on a 9x9 board, or after
+    /// them for regions. For other sizes, see `Geometry::unit_index`
+    pub fn index(self) -> usize {
+        match self {
+            Unit::Row(index) => index,
+            Unit::Column(index) => index + 9,
+            Unit::Box(index) => index + 18,
+            Unit::Region(index) => index + 27,
+        }
+    }
+}
+
+/// The spaces adjacent to each space. An adjacent space is one in the
+/// same row, column, or box, and each list is sorted
+///
+/// Spaces are indexed row by row, so the space at (row, column) is
+/// `row * 9 + column`
+///
+/// This and the tables below are for 9x9 boards, and are the same as the
+/// ones in its `Geometry`. For other sizes, see `Geometry`
+///
+/// ```
+/// use rsudoku::{Geometry, PEERS, UNITS, UNITS_OF_CELL};
+///
+/// let geometry = Geometry::new(3, 3).unwrap();
+///
+/// for index in 0..81 {
+///     assert_eq!(PEERS[index], geometry.peers(index));
+///     assert_eq!(UNITS_OF_CELL[index], geometry.units_of_cell(index));
+/// }
+///
+/// for (unit, spaces) in UNITS.iter().enumerate() {
+///     assert_eq!(spaces, &geometry.units()[unit][..]);
+/// }
+/// ```
+pub static PEERS: [[usize; 20]; 81] = build_peers();
+
+/// The spaces in each row, column, and box. Rows are units 0 to 8, columns
+/// are 9 to 17, and boxes are 18 to 26. See `Unit::from_index`
+///
+/// Spaces are listed left to right, then top to bottom
+pub static UNITS: [[usize; 9]; 27] = build_units();
+
+/// The indexes in `UNITS` of the row, column, and box of each space
+pub static UNITS_OF_CELL: [[usize; 3]; 81] = build_units_of_cell();
+
+/// Builds `UNITS` at compile time
+const fn build_units() -> [[usize; 9]; 27] {
+    let mut units = [[0; 9]; 27];
+
+    let mut i = 0;
+    while i < 9 {
+        let mut j = 0;
+        while j < 9 {
+            units[i][j] = i * 9 + j;
+            units[i + 9][j] = j * 9 + i;
+            units[i + 18][j] = (i / 3 * 3 + j / 3) * 9 + i % 3 * 3 + j % 3;
+
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    return units;
+}
+
+/// Builds `UNITS_OF_CELL` at compile time
+const fn build_units_of_cell() -> [[usize; 3]; 81] {
+    let mut units_of_cell = [[0; 3]; 81];
+
+    let mut index = 0;
+    while index < 81 {
+        let (row, column) = (index / 9, index % 9);
+        units_of_cell[index] = [row, column + 9, row / 3 * 3 + column / 3 + 18];
+
+        index += 1;
+    }
+
+    return units_of_cell;
+}
+
+/// Builds `PEERS` at compile time
+const fn build_peers() -> [[usize; 20]; 81] {
+    let units_of_cell = build_units_of_cell();
+    let mut peers = [[0; 20]; 81];
+
+    let mut index = 0;
+    while index < 81 {
+        let mut peers_found = 0;
+
+        let mut other = 0;
+        while other < 81 {
+            let same_row = units_of_cell[index][0] == units_of_cell[other][0];
+            let same_column = units_of_cell[index][1] == units_of_cell[other][1];
+            let same_box = units_of_cell[index][2] == units_of_cell[other][2];
+
+            if other != index && (same_row || same_column || same_box) {
+                peers[index][peers_found] = other;
+                peers_found += 1;
+            }
+
+            other += 1;
+        }
+
+        index += 1;
+    }
+
+    return peers;
+}
+
+/// Everything that can go wrong when reading or solving a sudoku
+///
+/// Points are (row, column) and start at 0. Lines and columns of parse
+/// errors start at 1, like in a text editor
+#[derive(Debug)]
+pub enum SudokuError {
+    /// The board couldn't be read
+    Io(io::Error),
+
+    /// A character that isn't a space, or whitespace or a line between
+    /// boxes. `spaces_found` is the number of spaces read before it
+    InvalidCharacter {
+        character: char,
+        line: usize,
+        column: usize,
+        spaces_found: usize,
+    },
+
+    /// The input ended before all the spaces were read
+    NotEnoughSpaces {
+        spaces_found: usize,
+        spaces_expected: usize,
+    },
+
+    /// Two known spaces in the same row, column, box, or region have the
+    /// same value
+    DuplicateGiven {
+        first: (usize, usize),
+        second: (usize, usize),
+        value: usize,
+    },
+
+    /// A value was filled into a space that couldn't have it
+    ValueNotPossible { point: (usize, usize), value: usize },
+
+    /// A space has no possible values left
+    NoPossibleValues { point: (usize, usize) },
+
+    /// No space in a row, column, box, or region can have a value
+    NoSpaceForValue { unit: Unit, value: usize },
+
+    /// A step of a trace couldn't be read, or replayed. Steps count from 1
+    InvalidStep { step: usize },
+
+    /// The sudoku has more than one solution, but needs to have one
+    MultipleSolutions,
+
+    /// Boxes can't be this size. Boards need at least one value, and at
+    /// most `Geometry::MAX_SIZE`
+    InvalidGeometry { box_width: usize, box_height: usize },
+
+    /// A symbol is blank, used twice, or has whitespace or a line between
+    /// boxes in it
+    InvalidSymbol { symbol: String },
+
+    /// There aren't enough symbols for every value of the board
+    NotEnoughSymbols {
+        symbols_found: usize,
+        symbols_expected: usize,
+    },
+
+    /// A constraint used a space that isn't on the board
+    OffBoard { point: (usize, usize) },
+
+    /// A technique can't be used on a board this big. See
+    /// `Technique::works_on`
+    TechniqueNotSupported { technique: Technique },
+
+    /// The generator used up its attempts without making a sudoku that
+    /// fits its settings. Each failed attempt either had more givens than
+    /// the max, or was the wrong difficulty
+    GenerateFailed {
+        attempts: usize,
+        too_many_givens: usize,
+        wrong_level: usize,
+    },
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Couldn't read board: {}", error),
+            Self::InvalidCharacter {
+                character,
+                line,
+                column,
+                spaces_found,
+            } => write!(
+                f,
+                "Invalid character {:?} at line {}, column {} after {} spaces",
+                character, line, column, spaces_found
+            ),
+            Self::NotEnoughSpaces {
+                spaces_found,
+                spaces_expected,
+            } => write!(
+                f,
+                "Found {} spaces, expected {}",
+                spaces_found, spaces_expected
+            ),
+            Self::DuplicateGiven {
+                first,
+                second,
+                value,
+            } => write!(
+                f,
+                "{} is given at both row {}, column {} and row {}, column {}",
+                value,
+                first.0 + 1,
+                first.1 + 1,
+                second.0 + 1,
+                second.1 + 1
+            ),
+            Self::ValueNotPossible { point, value } => write!(
+                f,
+                "{} isn't possible at row {}, column {}",
+                value,
+                point.0 + 1,
+                point.1 + 1
+            ),
+            Self::NoPossibleValues { point } => write!(
+                f,
+                "No possible values left at row {}, column {}",
+                point.0 + 1,
+                point.1 + 1
+            ),
+            Self::NoSpaceForValue { unit, value } => {
+                write!(f, "No space left for {} in {}", value, unit)
+            }
+            Self::InvalidStep { step } => write!(f, "Step {} of the trace is invalid", step),
+            Self::MultipleSolutions => write!(f, "The sudoku has more than one solution"),
+            Self::InvalidGeometry {
+                box_width,
+                box_height,
+            } => write!(f, "Boxes can't be {}x{}", box_width, box_height),
+            Self::InvalidSymbol { symbol } => write!(f, "{:?} can't be a symbol", symbol),
+            Self::NotEnoughSymbols {
+                symbols_found,
+                symbols_expected,
+            } => write!(
+                f,
+                "Found {} symbols, expected {}",
+                symbols_found, symbols_expected
+            ),
+            Self::OffBoard { point } => write!(
+                f,
+                "Row {}, column {} isn't on the board",
+                point.0 + 1,
+                point.1 + 1
+            ),
+            Self::TechniqueNotSupported { technique } => {
+                write!(f, "{} doesn't work on boards this big", technique)
+            }
+            Self::GenerateFailed {
+                attempts,
+                too_many_givens,
+                wrong_level,
+            } => write!(
+                f,
+                "Gave up after {} attempts: {} had too many givens, and {} were the wrong difficulty",
+                attempts, too_many_givens, wrong_level
+            ),
+        }
+    }
+}
+
+impl error::Error for SudokuError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SudokuError {
+    fn from(error: io::Error) -> Self {
+        return SudokuError::Io(error);
+    }
+}
 
 /// All of the results of removing a possible value from a space.
 ///
 /// Possible value was in the space,
 /// The possible value wasn't in the space,
-/// The value is now known. The SudokuValue is now a Known type
+/// The value is now known. The SudokuValue is now a Known type,
+/// The value removed was the last one. The sudoku is unsolvable
 enum SudokuValueResult {
     PossibleValueRemoved,
     PossibleValueAlreadyRemoved,
     ValueNowKnown,
+    NoPossibleValuesLeft,
+}
+
+/// A set of possible values of a space, stored as a bitmask. Bit `n` is
+/// set if `n` is a possible value, so bit 0 is never used
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default)]
+struct Candidates(u32);
+
+impl Candidates {
+    /// All the values 1 to `size`
+    fn all(size: usize) -> Candidates {
+        return Candidates((u32::MAX >> (Geometry::MAX_SIZE - size)) & !1);
+    }
+
+    /// Returns if `value` is in the set
+    fn contains(self, value: usize) -> bool {
+        return self.0 & (1 << value) != 0;
+    }
+
+    /// Adds `value` to the set
+    fn insert(&mut self, value: usize) {
+        self.0 |= 1 << value;
+    }
+
+    /// Removes `value` from the set. Returns if it was in the set
+    fn remove(&mut self, value: usize) -> bool {
+        let contained = self.contains(value);
+        self.0 &= !(1 << value);
+
+        return contained;
+    }
+
+    /// Returns the number of values in the set
+    fn len(self) -> usize {
+        return self.0.count_ones() as usize;
+    }
+
+    /// Returns the value if there's exactly one in the set
+    fn only_value(self) -> Option<usize> {
+        if self.len() == 1 {
+            return Some(self.0.trailing_zeros() as usize);
+        }
+
+        return None;
+    }
+
+    /// Returns an iterator over the values, smallest first
+    fn iter(self) -> CandidatesIter {
+        return CandidatesIter(self.0);
+    }
+}
+
+impl ops::BitOr for Candidates {
+    type Output = Candidates;
+
+    /// Returns the values in either set
+    fn bitor(self, other: Candidates) -> Candidates {
+        return Candidates(self.0 | other.0);
+    }
+}
+
+impl ops::BitAnd for Candidates {
+    type Output = Candidates;
+
+    /// Returns the values in both sets
+    fn bitand(self, other: Candidates) -> Candidates {
+        return Candidates(self.0 & other.0);
+    }
+}
+
+impl ops::Sub for Candidates {
+    type Output = Candidates;
+
+    /// Returns the values in this set, but not `other`
+    fn sub(self, other: Candidates) -> Candidates {
+        return Candidates(self.0 & !other.0);
+    }
+}
+
+impl fmt::Debug for Candidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.debug_set().entries(self.iter()).finish();
+    }
+}
+
+/// Iterator over the values in `Candidates`, smallest first
+struct CandidatesIter(u32);
+
+impl Iterator for CandidatesIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let value = self.0.trailing_zeros() as usize;
+
+        // Clear the lowest set bit
+        self.0 &= self.0 - 1;
+
+        return Some(value);
+    }
 }
 
 /**
  * A Sudoku space value
  *
  * Known with a digit,
- * Unknown with a vector of possible tests
+ * Unknown with the set of possible values
  */
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 enum SudokuValue {
-    Known(usize),
-    Unknown(Vec<usize>),
+    Known(u8),
+    Unknown(Candidates),
 }
 
 impl SudokuValue {
-    /// Create a new Sudoku value from a character.
+    /// Create a new Sudoku value from a symbol, for a board with `size`
+    /// values
     ///
-    /// If 'value' is a digit '1' to '9', then it will return a Known value
+    /// If 'symbol' is one of the first `size` symbols, then it will return
+    /// a Known value
     ///
-    /// If 'value' is a letter a-z or A-Z or '0' then it'll return an
-    /// Unknown value with possible values 1 to 9
+    /// If 'symbol' is the empty symbol, or a single letter or digit that
+    /// isn't one of the symbols, then it'll return an Unknown value with
+    /// possible values 1 to `size`. So boards using numbers can have '0' or
+    /// any letter for empty spaces
     ///
-    /// All other values return None
+    /// All other symbols return None
     ///
-    /// ```
-    /// assert_eq!(SudokuValue::new('3'), Some(Known(3)))
-    /// assert_eq!(SudokuValue::new('0'), Some(Unknown(vec![1,2,3,4,5,6,7,8,9])))
-    /// assert_eq!(SudokuValue::new('A'), Some(Unknown(vec![1,2,3,4,5,6,7,8,9])))
-    /// assert_eq!(SudokuValue::new('-'), None)
-    /// assert_eq!(SudokuValue::new('\t'), None)
+    /// ```ignore
+    /// let numbers = Symbols::numbers();
+    /// assert_eq!(SudokuValue::from("3", &numbers, 9), Some(Known(3)));
+    /// assert_eq!(SudokuValue::from("0", &numbers, 9), Some(Unknown(Candidates::all(9))));
+    /// assert_eq!(SudokuValue::from("A", &numbers, 9), Some(Unknown(Candidates::all(9))));
+    /// assert_eq!(SudokuValue::from("A", &Symbols::hex(), 16), Some(Known(11)));
+    /// assert_eq!(SudokuValue::from("7", &numbers, 6), None);
+    /// assert_eq!(SudokuValue::from("-", &numbers, 9), None);
     /// ```
     ///
-    fn from(value: char) -> Option<SudokuValue> {
-        match value {
-            '1'..='9' => {
-                return Some(SudokuValue::Known(value.to_digit(10).unwrap() as usize));
-            }
+    fn from(symbol: &str, symbols: &Symbols, size: usize) -> Option<SudokuValue> {
+        if symbol == symbols.empty() {
+            return Some(SudokuValue::Unknown(Candidates::all(size)));
+        }
+
+        match symbols.value_of(symbol) {
+            Some(value) if value <= size => return Some(SudokuValue::Known(value as u8)),
+            Some(_) => return None,
+            None => {}
+        }
+
+        let mut characters = symbol.chars();
 
-            'a'..='z' | 'A'..='Z' | '0' => {
-                return Some(SudokuValue::Unknown(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]));
+        match (characters.next(), characters.next()) {
+            (Some(character), None) if character.is_ascii_alphanumeric() => {
+                return Some(SudokuValue::Unknown(Candidates::all(size)));
             }
 
             _ => {
@@ -61,6 +531,42 @@ impl SudokuValue {
         }
     }
 
+    /// Create a new Sudoku value from a number. 0 is an empty space, and 1
+    /// to `size` are known values. Bigger numbers return None
+    fn from_number(value: usize, size: usize) -> Option<SudokuValue> {
+        match value {
+            0 => return Some(SudokuValue::Unknown(Candidates::all(size))),
+            _ if value <= size => return Some(SudokuValue::Known(value as u8)),
+            _ => return None,
+        }
+    }
+
+    /// Create a new Sudoku value from a byte.
+    ///
+    /// Bytes below 32 are read as the number itself, so 0 is an empty
+    /// space. ASCII doesn't use them for characters that could be spaces.
+    /// Any other byte is read as an ASCII character, with the same rules as
+    /// `SudokuValue::from` with the numbers
+    fn from_byte(value: u8, size: usize) -> Option<SudokuValue> {
+        match value {
+            0..=31 => SudokuValue::from_number(value as usize, size),
+            _ => SudokuValue::from(
+                char::from(value).encode_utf8(&mut [0; 4]),
+                &Symbols::standard(),
+                size,
+            ),
+        }
+    }
+
+    /// Returns the symbol for the value, or the empty symbol if it isn't
+    /// known
+    fn symbol<'a>(&self, symbols: &'a Symbols) -> &'a str {
+        match self {
+            Self::Known(value) => return &symbols.values()[*value as usize - 1],
+            Self::Unknown(_) => return symbols.empty(),
+        }
+    }
+
     /// Return if an Sudoku value is Known
     fn is_known(&self) -> bool {
         match self {
@@ -80,39 +586,31 @@ impl SudokuValue {
     /// For a Known space:
     /// - If possible_value_to_remove not the known value, returns
     ///   `PossibleValueAlreadyRemoved`
-    /// - Else, return `Result::Err(())`. Sudoku is unsolvable
+    /// - Else, returns `NoPossibleValuesLeft`. Sudoku is unsolvable
     ///     
-    fn remove(&mut self, possible_value_to_remove: usize) -> Result<SudokuValueResult, ()> {
+    fn remove(&mut self, possible_value_to_remove: usize) -> SudokuValueResult {
         match self {
             Self::Known(value) => {
-                if *value == possible_value_to_remove {
+                if *value as usize == possible_value_to_remove {
                     // Tried to remove a known value space. Sudoku is unsolvable
-                    return Result::Err(());
+                    return SudokuValueResult::NoPossibleValuesLeft;
                 } else {
-                    return Result::Ok(SudokuValueResult::PossibleValueAlreadyRemoved);
+                    return SudokuValueResult::PossibleValueAlreadyRemoved;
                 }
             }
 
             Self::Unknown(possible_values) => {
-                let possible_index = possible_values
-                    .iter()
-                    .position(|&x| x == possible_value_to_remove);
-
-                match possible_index {
-                    None => return Result::Ok(SudokuValueResult::PossibleValueAlreadyRemoved),
+                if !possible_values.remove(possible_value_to_remove) {
+                    return SudokuValueResult::PossibleValueAlreadyRemoved;
+                }
 
-                    Some(index) => {
-                        // Remove the possible value from the vector
-                        possible_values.swap_remove(index);
-
-                        // Check the length, to see if the value can be known
-                        if possible_values.len() == 1 {
-                            *self = SudokuValue::Known(possible_values[0]);
-                            return Result::Ok(SudokuValueResult::ValueNowKnown);
-                        } else {
-                            return Result::Ok(SudokuValueResult::PossibleValueRemoved);
-                        }
+                // Check the number of values left, to see if the value can be known
+                match possible_values.only_value() {
+                    Some(value) => {
+                        *self = SudokuValue::Known(value as u8);
+                        return SudokuValueResult::ValueNowKnown;
                     }
+                    None => return SudokuValueResult::PossibleValueRemoved,
                 }
             }
         }
@@ -120,129 +618,397 @@ impl SudokuValue {
 }
 
 impl fmt::Display for SudokuValue {
+    /// Prints the value with the standard symbols, so 1 to 9 and up, or 'X'
+    /// if it isn't known
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Known(x) => {
-                write!(f, "{}", x)
-            }
-            Self::Unknown(_) => {
-                write!(f, "X")
-            }
+        write!(f, "{}", self.symbol(&Symbols::standard()))
+    }
+}
+
+/// A queue of known spaces that still need their value removed from
+/// adjacent spaces. Used by `SudokuBoard::propagate`
+///
+/// A space is only added when it becomes known, which happens once, so it
+/// never needs to wrap around. The first 81 spaces are kept in an array, so
+/// 9x9 boards never allocate, and any more go in a `Vec`
+struct KnownSpaceQueue {
+    spaces: [u16; 81],
+    overflow: Vec<u16>,
+    start: usize,
+    end: usize,
+}
+
+impl KnownSpaceQueue {
+    /// Create an empty queue
+    fn new() -> Self {
+        return KnownSpaceQueue {
+            spaces: [0; 81],
+            overflow: Vec::new(),
+            start: 0,
+            end: 0,
+        };
+    }
+
+    /// Add a space index to the back of the queue
+    fn push(&mut self, index: usize) {
+        match self.spaces.get_mut(self.end) {
+            Some(space) => *space = index as u16,
+            None => self.overflow.push(index as u16),
+        }
+
+        self.end += 1;
+    }
+
+    /// Take the space index from the front of the queue
+    fn pop(&mut self) -> Option<usize> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+
+        match self.spaces.get(self.start - 1) {
+            Some(&space) => return Some(space as usize),
+            None => return Some(self.overflow[self.start - 1 - self.spaces.len()] as usize),
         }
     }
 }
 
+/// The number of solutions a sudoku has, from
+/// `SudokuBoard::count_solutions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionCount {
+    /// The sudoku has exactly this many solutions
+    Exactly(usize),
+
+    /// The limit was reached. The sudoku has this many solutions or more
+    AtLeast(usize),
+}
+
 /**
- * A Sudoku board. Contains the sudoku spaces in a 2D vector, and the
- * number of emtpy spaces. An empty_spaces option of 0 means the sudoku
- * is solved
+ * A Sudoku board. Contains the sudoku spaces row by row, and the number of
+ * emtpy spaces. An empty_spaces option of 0 means the sudoku is solved
+ *
+ * The geometry is shared between copies of the board, so copying it for a
+ * guess only copies the spaces
  */
 #[derive(Debug, Clone)]
 pub struct SudokuBoard {
-    spaces: Vec<Vec<SudokuValue>>,
+    spaces: Vec<SudokuValue>,
     empty_spaces: usize,
-    initialized: bool,
-}
 
-impl SudokuBoard {
-    /// Create a new sudoku board from a file
-    ///
-    /// `board_filepath` should be a path to the board file
-    fn new(board_filepath: &str) -> io::Result<Self> {
-        let board_string = fs::read_to_string(board_filepath)?;
+    /// The shape of the board, and its rows, columns, and boxes
+    geometry: Arc<Geometry>,
 
-        let mut spaces = Vec::with_capacity(9);
+    /// The symbols the board is read and printed with
+    symbols: Arc<Symbols>,
 
-        let mut row_index = 0;
-        let mut empty_spaces = 0;
-        let mut known_spaces = 0;
+    /// The rules the board has on top of its rows, columns, and boxes
+    constraints: Arc<Constraints>,
 
-        for character in board_string.chars() {
-            // Make a Sudoku value from a character
-            if let Some(value) = SudokuValue::from(character) {
-                if value.is_known() {
-                    known_spaces += 1;
-                } else {
-                    empty_spaces += 1;
-                }
+    initialized: bool,
 
-                if spaces.len() == row_index {
-                    spaces.push(Vec::with_capacity(9));
-                }
+    /// The techniques narrowing can use before guessing
+    techniques: TechniqueSet,
 
-                spaces[row_index].push(value);
+    /// If techniques that need the sudoku to have one solution can be used
+    assume_unique_solution: bool,
+}
 
-                if spaces[row_index].len() == 9 {
-                    row_index += 1;
-                    if row_index == 9 {
-                        break;
-                    }
-                }
-            }
+impl SudokuBoard {
+    /// Create a new sudoku board from a sequence of sudoku values
+    ///
+    /// Values are read row by row, starting at the top left. Only as many
+    /// values as the board has spaces are used. Returns an error if there
+    /// are fewer
+    fn from_values(
+        values: impl IntoIterator<Item = SudokuValue>,
+        geometry: Arc<Geometry>,
+    ) -> Result<Self, SudokuError> {
+        let spaces: Vec<SudokuValue> = values.into_iter().take(geometry.spaces()).collect();
+
+        if spaces.len() != geometry.spaces() {
+            return Result::Err(SudokuError::NotEnoughSpaces {
+                spaces_found: spaces.len(),
+                spaces_expected: geometry.spaces(),
+            });
         }
 
-        if known_spaces + empty_spaces != 81 {
-            return io::Result::Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "Failed to fill board",
-            ));
-        }
+        let empty_spaces = spaces.iter().filter(|space| !space.is_known()).count();
 
-        return io::Result::Ok(SudokuBoard {
+        return Result::Ok(SudokuBoard {
             spaces,
             empty_spaces,
+            geometry,
+            symbols: Symbols::standard(),
+            constraints: Constraints::none(),
             initialized: false,
+            techniques: TechniqueSet::default(),
+            assume_unique_solution: false,
         });
     }
 
-    /// Returns if the number of empty spaces is zero
-    pub fn is_solved(&self) -> bool {
-        return self.empty_spaces == 0;
+    /// Create a new sudoku board from bytes, where every byte must be a
+    /// space. See `SudokuValue::from_byte` for how bytes are read
+    ///
+    /// Invalid bytes are reported with the row and column as the line and
+    /// column
+    fn from_bytes<'a>(
+        bytes: impl IntoIterator<Item = &'a u8>,
+        geometry: Arc<Geometry>,
+    ) -> Result<Self, SudokuError> {
+        let mut values = Vec::with_capacity(geometry.spaces());
+
+        for &byte in bytes {
+            match SudokuValue::from_byte(byte, geometry.size()) {
+                Some(value) => values.push(value),
+                None => {
+                    let (row, column) = geometry.point_of(values.len());
+
+                    return Result::Err(SudokuError::InvalidCharacter {
+                        character: char::from(byte),
+                        line: row + 1,
+                        column: column + 1,
+                        spaces_found: values.len(),
+                    });
+                }
+            }
+        }
+
+        return SudokuBoard::from_values(values, geometry);
+    }
+
+    /// Create a new sudoku board of any shape from its spaces, read row by
+    /// row
+    ///
+    /// Each space is 0 for an empty space, or 1 to the size of the board for
+    /// a known one. ASCII characters are also accepted, like
+    /// `TryFrom<[[u8; 9]; 9]>`
+    ///
+    /// ```
+    /// use rsudoku::{Geometry, SudokuBoard};
+    ///
+    /// let grid = [
+    ///     1, 0, 0, 0,
+    ///     0, 0, 3, 0,
+    ///     0, 4, 0, 0,
+    ///     0, 0, 0, 2,
+    /// ];
+    ///
+    /// let mut board = SudokuBoard::from_grid(&grid, Geometry::new(2, 2).unwrap()).unwrap();
+    /// assert!(board.solve());
+    /// ```
+    pub fn from_grid(grid: &[u8], geometry: Geometry) -> Result<Self, SudokuError> {
+        return SudokuBoard::from_bytes(grid, Arc::new(geometry));
     }
 
-    /// Static method
+    /// Create a new sudoku board of any shape from text, using numbers for
+    /// the values. See `SudokuBoard::from_str` for how the text is read
     ///
-    /// Returns the cordients of the spaces adjecent to the input space
+    /// ```
+    /// use rsudoku::{Geometry, SudokuBoard};
     ///
-    /// An Adjectent space is a space that is in the same row, column, or
-    /// box. All entries are unique
+    /// // Boxes 3 wide and 2 tall
+    /// let board = SudokuBoard::from_str_with_geometry(
+    ///     "
+    ///     1 0 0 | 0 0 0
+    ///     0 0 0 | 0 5 0
+    ///     -------------
+    ///     0 3 0 | 0 0 0
+    ///     0 0 0 | 0 2 0
+    ///     -------------
+    ///     0 6 0 | 0 0 0
+    ///     0 0 0 | 4 0 0",
+    ///     Geometry::new(3, 2).unwrap(),
+    /// )
+    /// .unwrap();
     ///
-    /// (0, 0) is top left, (8, 0) is top right
-    fn get_adjecent_spaces(point: (usize, usize)) -> Vec<(usize, usize)> {
-        let mut adjecent_spaces = Vec::with_capacity(20);
+    /// assert_eq!(board.geometry().size(), 6);
+    /// ```
+    pub fn from_str_with_geometry(
+        board_string: &str,
+        geometry: Geometry,
+    ) -> Result<Self, SudokuError> {
+        return SudokuBoard::parse(board_string, Arc::new(geometry), Symbols::standard());
+    }
 
-        // Add the rows and columns
-        for i in 0..9 {
-            if i != point.0 {
-                adjecent_spaces.push((i, point.1));
-            }
+    /// Create a new sudoku board of any shape from a file
+    pub fn from_file_with_geometry<P: AsRef<Path>>(
+        board_filepath: P,
+        geometry: Geometry,
+    ) -> Result<Self, SudokuError> {
+        let board_string = fs::read_to_string(board_filepath)?;
 
-            if i != point.1 {
-                adjecent_spaces.push((point.0, i));
-            }
+        return SudokuBoard::from_str_with_geometry(&board_string, geometry);
+    }
+
+    /// Create a new sudoku board of any shape from text, with its own
+    /// symbols. The board keeps the symbols, and prints with them
+    ///
+    /// ```
+    /// use rsudoku::{Geometry, SudokuBoard, Symbols};
+    ///
+    /// let symbols = Symbols::from_chars("♠♥♦♣", '.').unwrap();
+    /// let mut board = SudokuBoard::from_str_with_symbols(
+    ///     "♠... ..♦. .♣.. ...♥",
+    ///     Geometry::new(2, 2).unwrap(),
+    ///     symbols,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(board.solve());
+    /// assert!(board.to_string().starts_with("♠ ♦ | ♥ ♣"));
+    /// ```
+    pub fn from_str_with_symbols(
+        board_string: &str,
+        geometry: Geometry,
+        symbols: Symbols,
+    ) -> Result<Self, SudokuError> {
+        SudokuBoard::check_symbols(&geometry, &symbols)?;
+
+        return SudokuBoard::parse(board_string, Arc::new(geometry), Arc::new(symbols));
+    }
+
+    /// Create a new sudoku board of any shape from a file, with its own
+    /// symbols
+    pub fn from_file_with_symbols<P: AsRef<Path>>(
+        board_filepath: P,
+        geometry: Geometry,
+        symbols: Symbols,
+    ) -> Result<Self, SudokuError> {
+        let board_string = fs::read_to_string(board_filepath)?;
+
+        return SudokuBoard::from_str_with_symbols(&board_string, geometry, symbols);
+    }
+
+    /// Returns `NotEnoughSymbols` if there isn't a symbol for every value
+    fn check_symbols(geometry: &Geometry, symbols: &Symbols) -> Result<(), SudokuError> {
+        if symbols.values().len() < geometry.size() {
+            return Result::Err(SudokuError::NotEnoughSymbols {
+                symbols_found: symbols.values().len(),
+                symbols_expected: geometry.size(),
+            });
         }
 
-        // Add box spaces not in rows or columns
-        let mut box_x = Vec::with_capacity(2);
-        let mut box_y = Vec::with_capacity(2);
+        return Result::Ok(());
+    }
 
-        for j in 0..3 {
-            if point.0 % 3 != j {
-                box_x.push(j + (point.0 / 3) * 3)
+    /// Parse a board from text. Boards where every symbol is one character
+    /// are read a character at a time, and others a symbol at a time
+    fn parse(
+        board_string: &str,
+        geometry: Arc<Geometry>,
+        symbols: Arc<Symbols>,
+    ) -> Result<Self, SudokuError> {
+        let mut values = Vec::with_capacity(geometry.spaces());
+        let single_characters = symbols.single_characters(geometry.size());
+        let mut diagonal = false;
+
+        'lines: for (line_index, line) in board_string.lines().enumerate() {
+            if values.len() == geometry.spaces() {
+                break;
             }
 
-            if point.1 % 3 != j {
-                box_y.push(j + (point.1 / 3) * 3)
+            if line.trim() == "# diagonal" {
+                diagonal = true;
+                continue;
             }
-        }
 
-        for x in &box_x {
-            for y in &box_y {
-                adjecent_spaces.push((*x, *y));
+            for (column_index, token) in tokens(line, single_characters) {
+                if values.len() == geometry.spaces() {
+                    break 'lines;
+                }
+
+                match SudokuValue::from(token, &symbols, geometry.size()) {
+                    Some(value) => values.push(value),
+                    None => {
+                        let invalid_character = token
+                            .chars()
+                            .find(|&character| !Symbols::is_layout_character(character));
+
+                        if let Some(character) = invalid_character {
+                            return Result::Err(SudokuError::InvalidCharacter {
+                                character,
+                                line: line_index + 1,
+                                column: column_index + 1,
+                                spaces_found: values.len(),
+                            });
+                        }
+                    }
+                }
             }
         }
 
-        return adjecent_spaces;
+        let mut board = SudokuBoard::from_values(values, geometry)?;
+        board.symbols = symbols;
+        board.set_diagonal(diagonal);
+
+        return Result::Ok(board);
+    }
+
+    /// Returns the shape of the board
+    pub fn geometry(&self) -> &Geometry {
+        return &self.geometry;
+    }
+
+    /// Returns the symbols the board is printed with
+    pub fn symbols(&self) -> &Symbols {
+        return &self.symbols;
+    }
+
+    /// Changes the symbols the board is printed with
+    ///
+    /// Returns `NotEnoughSymbols` if there isn't a symbol for every value
+    pub fn set_symbols(&mut self, symbols: Symbols) -> Result<(), SudokuError> {
+        SudokuBoard::check_symbols(&self.geometry, &symbols)?;
+        self.symbols = Arc::new(symbols);
+
+        return Result::Ok(());
+    }
+
+    /// Prints the board with other symbols, without changing the ones it
+    /// has. See `SudokuBoard::set_symbols`
+    ///
+    /// ```
+    /// use rsudoku::{Geometry, SudokuBoard, Symbols};
+    ///
+    /// let grid = [1, 0, 0, 0, 0, 0, 3, 0, 0, 4, 0, 0, 0, 0, 0, 2];
+    /// let board = SudokuBoard::from_grid(&grid, Geometry::new(2, 2).unwrap()).unwrap();
+    ///
+    /// let letters = board.to_string_with_symbols(&Symbols::letters()).unwrap();
+    /// assert!(letters.starts_with("A . | . ."));
+    /// assert!(board.to_string().starts_with("1 X | X X"));
+    /// ```
+    ///
+    /// Returns `NotEnoughSymbols` if there isn't a symbol for every value
+    pub fn to_string_with_symbols(&self, symbols: &Symbols) -> Result<String, SudokuError> {
+        let mut board = self.clone();
+        board.set_symbols(symbols.clone())?;
+
+        return Result::Ok(board.to_string());
+    }
+
+    /// Create a new sudoku board from a reader
+    ///
+    /// The whole reader is read as text, and parsed the same way as
+    /// `SudokuBoard::from_str`
+    pub fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, SudokuError> {
+        let mut board_string = String::new();
+        reader.read_to_string(&mut board_string)?;
+
+        return board_string.parse();
+    }
+
+    /// Create a new sudoku board from a file
+    ///
+    /// `board_filepath` should be a path to the board file
+    pub fn from_file<P: AsRef<Path>>(board_filepath: P) -> Result<Self, SudokuError> {
+        return SudokuBoard::from_reader(fs::File::open(board_filepath)?);
+    }
+
+    /// Returns if the number of empty spaces is zero
+    pub fn is_solved(&self) -> bool {
+        return self.empty_spaces == 0;
     }
 
     /// Function that checks all known spaces and removes their value from
@@ -250,96 +1016,198 @@ impl SudokuBoard {
     ///
     /// May also change unknown spaces to known spaces, and also removes
     /// these values from adjectent spaces
-    fn initial_check(&mut self) -> Result<(), String> {
+    ///
+    /// Returns `DuplicateGiven` if two known spaces see each other with the
+    /// same value
+    fn initial_check(&mut self) -> Result<(), SudokuError> {
         // Set self be initalised. This method only needs to be run once on each board
         self.initialized = true;
 
-        // Queue of known points. The 3 value tuple is it's row index,
-        // column index, and space value
-        let mut known_points_to_check: VecDeque<(usize, usize, usize)> = VecDeque::new();
+        self.check_duplicate_givens()?;
 
         // Add all the currently known spaces to the queue
-        for (i, row) in self.spaces.iter().enumerate() {
-            for (j, space) in row.iter().enumerate() {
-                if let SudokuValue::Known(space_value) = space {
-                    known_points_to_check.push_back((i, j, *space_value));
-                }
+        let mut known_spaces_to_check = KnownSpaceQueue::new();
+
+        for (index, space) in self.spaces.iter().enumerate() {
+            if space.is_known() {
+                known_spaces_to_check.push(index);
             }
         }
 
-        while !known_points_to_check.is_empty() {
-            let (x, y, space_value) = known_points_to_check.pop_front().expect("Queue is empty!");
+        self.propagate(&mut known_spaces_to_check)?;
+        self.apply_constraints()?;
 
-            for adjecent_space in SudokuBoard::get_adjecent_spaces((x, y)) {
+        return Result::Ok(());
+    }
+
+    /// Removes the value of each known space in the queue from its
+    /// adjacent spaces, including ones in the same region of a constraint.
+    /// Spaces that become known are added to the queue, and this carries on
+    /// until the queue is empty
+    ///
+    /// This is the only place values are removed from adjacent spaces, so
+    /// there's no recursion however many spaces become known
+    ///
+    /// Returns the number of new known spaces, or an error if a space is
+    /// left with no possible values
+    fn propagate(
+        &mut self,
+        known_spaces_to_check: &mut KnownSpaceQueue,
+    ) -> Result<usize, SudokuError> {
+        let mut new_known = 0;
+
+        // Shared, so the peers can be read while spaces change
+        let geometry = Arc::clone(&self.geometry);
+        let constraints = Arc::clone(&self.constraints);
+
+        while let Some(index) = known_spaces_to_check.pop() {
+            let value = match self.get_space(index) {
+                SudokuValue::Known(value) => *value as usize,
+                SudokuValue::Unknown(_) => panic!("Space should be Known"),
+            };
+
+            for &adjacent_index in constraints.peers(&geometry, index) {
                 // Remove it, and get the result of removeing it
-                let remove_result =
-                    self.spaces[adjecent_space.0][adjecent_space.1].remove(space_value);
+                let remove_result = self.remove_possible_value(adjacent_index, value)?;
 
-                if remove_result.is_err() {
-                    return Result::Err(String::from("Sudoku board is unsolvable"));
+                if let SudokuValueResult::ValueNowKnown = remove_result {
+                    new_known += 1;
+                    self.empty_spaces -= 1;
+
+                    known_spaces_to_check.push(adjacent_index);
                 }
+            }
+        }
 
-                if let SudokuValueResult::ValueNowKnown = remove_result.unwrap() {
-                    self.empty_spaces -= 1;
+        return Result::Ok(new_known);
+    }
 
-                    match self.spaces[adjecent_space.0][adjecent_space.1] {
-                        SudokuValue::Known(adjecent_space_value) => {
-                            known_points_to_check.push_back((
-                                adjecent_space.0,
-                                adjecent_space.1,
-                                adjecent_space_value,
-                            ));
-                        }
+    /// Returns the possible values the board's constraints rule out, as
+    /// (index, value), leaving out any that are already ruled out
+    ///
+    /// Returns `OffBoard` if a constraint rules out a value of a space that
+    /// isn't on the board
+    pub(crate) fn constraint_eliminations(&self) -> Result<Vec<(usize, usize)>, SudokuError> {
+        let mut eliminations = Vec::new();
+
+        for constraint in &self.constraints.list {
+            for (point, value) in constraint.eliminations(self) {
+                let size = self.geometry.size();
+
+                if !self.geometry.contains(point) {
+                    return Result::Err(SudokuError::OffBoard { point });
+                }
+
+                let index = self.geometry.index_of(point);
 
-                        _ => panic!("Space should be Known"),
+                let possible = match self.get_space(index) {
+                    SudokuValue::Known(known_value) => *known_value as usize == value,
+                    SudokuValue::Unknown(possible_values) => {
+                        (1..=size).contains(&value) && possible_values.contains(value)
                     }
+                };
+
+                if possible && !eliminations.contains(&(index, value)) {
+                    eliminations.push((index, value));
                 }
             }
         }
 
-        return Result::Ok(());
+        return Result::Ok(eliminations);
     }
 
-    /// Returns a 2d vector of points.
+    /// Removes the possible values the board's constraints rule out, and
+    /// any known values from adjacent spaces, until there's nothing left to
+    /// remove
     ///
-    /// Each vector of points are the postion of values in a single row,
-    /// column, or 3x3 box
-    fn get_all_full_corrdinates() -> Vec<Vec<(usize, usize)>> {
-        let mut output: Vec<Vec<(usize, usize)>> = Vec::with_capacity(27);
+    /// Returns the number of new known spaces, or an error if the board
+    /// can't follow the constraints
+    fn apply_constraints(&mut self) -> Result<usize, SudokuError> {
+        let mut new_known = 0;
 
-        // Add rows and columns
-        for i in 0..9 {
-            let mut row: Vec<(usize, usize)> = Vec::with_capacity(9);
-            let mut column: Vec<(usize, usize)> = Vec::with_capacity(9);
-            for j in 0..9 {
-                row.push((i, j));
-                column.push((j, i));
+        if self.constraints.list.is_empty() {
+            return Result::Ok(new_known);
+        }
+
+        loop {
+            let eliminations = self.constraint_eliminations()?;
+
+            if eliminations.is_empty() {
+                return Result::Ok(new_known);
+            }
+
+            let mut known_spaces_to_check = KnownSpaceQueue::new();
+
+            for (index, value) in eliminations {
+                if let SudokuValueResult::ValueNowKnown =
+                    self.remove_possible_value(index, value)?
+                {
+                    new_known += 1;
+                    self.empty_spaces -= 1;
+
+                    known_spaces_to_check.push(index);
+                }
+            }
+
+            new_known += self.propagate(&mut known_spaces_to_check)?;
+        }
+    }
+
+    /// Returns the first pair of known spaces that see each other and have
+    /// the same value as an error
+    fn check_duplicate_givens(&self) -> Result<(), SudokuError> {
+        for unit in self
+            .geometry
+            .units()
+            .iter()
+            .chain(&self.constraints.regions)
+        {
+            // Where each value was first seen in the set
+            let mut first_seen = [None; Geometry::MAX_SIZE + 1];
+
+            for &index in unit {
+                if let SudokuValue::Known(value) = *self.get_space(index) {
+                    let value = value as usize;
+
+                    if let Some(first) = first_seen[value] {
+                        return Result::Err(SudokuError::DuplicateGiven {
+                            first: self.geometry.point_of(first),
+                            second: self.geometry.point_of(index),
+                            value,
+                        });
+                    }
+
+                    first_seen[value] = Some(index);
+                }
             }
-
-            output.push(row);
-            output.push(column);
         }
 
-        // Add 3 by 3 boxes
-        for box_x_index in 0..3 {
-            for box_y_index in 0..3 {
-                let mut sudoku_box: Vec<(usize, usize)> = Vec::with_capacity(9);
-
-                for i in 0..9 {
-                    sudoku_box.push((box_x_index * 3 + i % 3, box_y_index * 3 + i / 3))
-                }
+        return Result::Ok(());
+    }
 
-                output.push(sudoku_box);
+    /// Removes a possible value from a space. See `SudokuValue::remove`
+    ///
+    /// Returns `NoPossibleValues` if it was the last value of the space
+    fn remove_possible_value(
+        &mut self,
+        index: usize,
+        value: usize,
+    ) -> Result<SudokuValueResult, SudokuError> {
+        match self.get_space_mut(index).remove(value) {
+            SudokuValueResult::NoPossibleValuesLeft => {
+                return Result::Err(SudokuError::NoPossibleValues {
+                    point: self.geometry.point_of(index),
+                });
             }
+            result => return Result::Ok(result),
         }
-
-        return output;
     }
 
     /// Fill in a sudoku space with a value
     ///
-    /// Also remove this value from adject sudoku spaces, and if the value
-    /// is now known, recesivlly removes and checks new known values
+    /// Also remove this value from adject sudoku spaces, and if any of them
+    /// are now known, removes and checks their values too. See `propagate`.
+    /// Then removes anything the constraints rule out
     ///
     /// If space is already known, checks to see if the value is correct,
     /// then proform the checks as above
@@ -347,55 +1215,52 @@ impl SudokuBoard {
     /// Returns the number of new known spaces
     ///
     /// If filling in any space is invalid, then return Result::Err
-    fn fill_space(&mut self, point: (usize, usize), value: usize) -> Result<usize, String> {
+    fn fill_space(&mut self, index: usize, value: usize) -> Result<usize, SudokuError> {
         let mut new_known = 0;
 
-        // Fill in the space
-        match &self.spaces[point.0][point.1] {
-            SudokuValue::Known(x) => {
-                if *x != value {
-                    return Result::Err(String::from("Tried to overright in a known value"));
-                } else {
-                    // The function should still check adjectent values and remove these.
-                }
-            }
-
-            SudokuValue::Unknown(possible) => {
-                if possible.contains(&value) {
-                    // Repalce the value
-                    self.spaces[point.0][point.1] = SudokuValue::Known(value);
-                    new_known += 1;
-                    self.empty_spaces -= 1;
-                } else {
-                    return Result::Err(String::from("Not a possible value"));
-                }
-            }
+        if self.set_known(index, value)? {
+            new_known += 1;
         }
 
-        for point_to_check in SudokuBoard::get_adjecent_spaces(point) {
-            let space_to_check = &mut self.spaces[point_to_check.0][point_to_check.1];
+        let mut known_spaces_to_check = KnownSpaceQueue::new();
+        known_spaces_to_check.push(index);
 
-            // Remove known value from possible values, and check the result
-            let removed_result = space_to_check.remove(value);
+        new_known += self.propagate(&mut known_spaces_to_check)?;
+        new_known += self.apply_constraints()?;
 
-            if removed_result.is_err() {
-                return Result::Err(String::from("Sudoku is unsolvable"));
-            }
+        return Result::Ok(new_known);
+    }
+
+    /// Sets a space to a known value, without removing it from adjacent
+    /// spaces
+    ///
+    /// Returns if the space was unknown before, or an error if it can't be
+    /// the value
+    fn set_known(&mut self, index: usize, value: usize) -> Result<bool, SudokuError> {
+        let point = self.geometry.point_of(index);
+
+        match self.get_space(index) {
+            SudokuValue::Known(x) => {
+                if *x as usize != value {
+                    return Result::Err(SudokuError::ValueNotPossible { point, value });
+                }
 
-            // If the value can now be known, add it to a vector to be checked later
-            if let SudokuValueResult::ValueNowKnown = removed_result.unwrap() {
-                if let SudokuValue::Known(checked_known_value) = space_to_check {
-                    let owned_checked_known_value = *checked_known_value;
+                // The space is already known, but adjacent values still
+                // need checking
+                return Result::Ok(false);
+            }
 
-                    self.fill_space(point_to_check, owned_checked_known_value)?;
+            SudokuValue::Unknown(possible) => {
+                if !possible.contains(value) {
+                    return Result::Err(SudokuError::ValueNotPossible { point, value });
                 }
 
-                new_known += 1;
+                *self.get_space_mut(index) = SudokuValue::Known(value as u8);
                 self.empty_spaces -= 1;
+
+                return Result::Ok(true);
             }
         }
-
-        return Result::Ok(new_known);
     }
 
     /// Narrow down the possible values of empty spaces, filling in any
@@ -403,34 +1268,49 @@ impl SudokuBoard {
     ///
     /// Does the following tests:
     ///
-    /// - Check each row, column, and box. If there's exactly one space
-    ///   that can have a digit, fill in that digit. If zero places can have
-    ///   that digit, return an error
+    /// - Check each row, column, and box, and each region of a constraint
+    ///   with a space for every value. If there's exactly one space that
+    ///   can have a digit, fill in that digit. If zero places can have that
+    ///   digit, return an error
     ///
     /// Returns the number of new known spaces. Further narrowing may be
     /// done if the result is higher then zero
     ///
     /// If the sudoku is unsolvable, return an errors
-    fn narrow(&mut self) -> Result<usize, String> {
+    fn narrow(&mut self) -> Result<usize, SudokuError> {
         let mut new_spaces_known = 0;
 
-        // For each row, column, and box
-        for space_set_corrdinates in SudokuBoard::get_all_full_corrdinates() {
-            'values: for value in 1..=9 {
+        // Shared, so the units can be read while spaces change
+        let geometry = Arc::clone(&self.geometry);
+        let constraints = Arc::clone(&self.constraints);
+
+        // For each row, column, box, and region
+        for (unit_index, unit) in geometry
+            .units()
+            .iter()
+            .chain(&constraints.regions)
+            .enumerate()
+        {
+            // Smaller regions don't have to have every value
+            if unit.len() != geometry.size() {
+                continue;
+            }
+
+            'values: for value in 1..=geometry.size() {
                 // Possition of unknown value to fill in
-                let mut unknown_value_to_fill_in: Option<(usize, usize)> = None;
+                let mut unknown_value_to_fill_in: Option<usize> = None;
 
-                for point in &space_set_corrdinates {
-                    match &self.spaces[point.0][point.1] {
+                for &index in unit {
+                    match self.get_space(index) {
                         // Check the next value if known value already in the set
                         SudokuValue::Known(known_value) => {
-                            if *known_value == value {
+                            if *known_value as usize == value {
                                 continue 'values;
                             }
                         }
 
                         SudokuValue::Unknown(possible_values) => {
-                            if possible_values.contains(&value) {
+                            if possible_values.contains(value) {
                                 match unknown_value_to_fill_in {
                                     // More than one unknown space with
                                     // the same possible value, check
@@ -438,7 +1318,7 @@ impl SudokuBoard {
                                     Some(_) => {
                                         continue 'values;
                                     }
-                                    None => unknown_value_to_fill_in = Some(*point),
+                                    None => unknown_value_to_fill_in = Some(index),
                                 }
                             }
                         }
@@ -450,12 +1330,17 @@ impl SudokuBoard {
                     // box that couldn't contain a value not already known
                     // in that row, column, or box. This is invalid, so
                     // the sudoku is unsolvable
-                    None => return Result::Err(String::from("Sudoku is unsolvable")),
+                    None => {
+                        return Result::Err(SudokuError::NoSpaceForValue {
+                            unit: geometry.unit(unit_index),
+                            value,
+                        })
+                    }
 
                     // This value can be filled in. Return any error it
                     // might raise. Update the number of new spaces known
-                    Some(point) => {
-                        new_spaces_known += self.fill_space(point, value)?;
+                    Some(index) => {
+                        new_spaces_known += self.fill_space(index, value)?;
                     }
                 }
             }
@@ -471,15 +1356,242 @@ impl SudokuBoard {
     /// Returns if sudoku is now solved
     ///
     /// Returns Err if sudoku is unsolvable
-    fn narrow_full(&mut self) -> Result<bool, String> {
-        while self.narrow()? > 0 && !self.is_solved() {}
+    fn narrow_full(&mut self) -> Result<bool, SudokuError> {
+        loop {
+            while self.narrow()? > 0 && !self.is_solved() {}
+
+            // Once narrowing gets stuck, try the other techniques
+            if self.is_solved() || !self.apply_techniques()? {
+                return Result::Ok(self.is_solved());
+            }
+        }
+    }
+
+    /// Applies the first deduction found by the techniques the board is
+    /// allowed to use, trying the easiest techniques first
+    ///
+    /// Returns if a deduction was found
+    fn apply_techniques(&mut self) -> Result<bool, SudokuError> {
+        for technique in self.techniques.iter() {
+            if let Some(deduction) = techniques::find(self, technique) {
+                self.apply_deduction(&deduction)?;
+                return Result::Ok(true);
+            }
+        }
+
+        return Result::Ok(false);
+    }
+
+    /// Returns the techniques the solver can use before guessing
+    pub fn techniques(&self) -> TechniqueSet {
+        return self.techniques;
+    }
+
+    /// Sets the techniques the solver can use before guessing. By default,
+    /// it can use `TechniqueSet::default()`
+    ///
+    /// Single values are always filled in, so `TechniqueSet::empty()`
+    /// leaves just narrowing and guessing
+    ///
+    /// Returns `TechniqueNotSupported` if one of the techniques doesn't
+    /// work on boards this big, and leaves the techniques as they were
+    pub fn set_techniques(&mut self, techniques: TechniqueSet) -> Result<(), SudokuError> {
+        if let Some(technique) = techniques
+            .iter()
+            .find(|technique| !technique.works_on(&self.geometry))
+        {
+            return Result::Err(SudokuError::TechniqueNotSupported { technique });
+        }
+
+        self.techniques = techniques;
+
+        return Result::Ok(());
+    }
+
+    /// Returns the constraints the board has on top of its rows, columns,
+    /// and boxes, in the order they were added
+    pub fn constraints(&self) -> impl Iterator<Item = &dyn Constraint> {
+        return self.constraints.list.iter().map(|constraint| &**constraint);
+    }
+
+    /// Adds a constraint the solution has to follow. See `Constraint`
+    ///
+    /// Returns `OffBoard` if the constraint, or one of its regions, has a
+    /// space that isn't on the board
+    pub fn add_constraint(
+        &mut self,
+        constraint: impl Constraint + 'static,
+    ) -> Result<(), SudokuError> {
+        Arc::make_mut(&mut self.constraints).add(Arc::new(constraint), &self.geometry)?;
+
+        // The known spaces need removing from their new peers
+        self.initialized = false;
+
+        return Result::Ok(());
+    }
 
-        return Result::Ok(self.is_solved());
+    /// Returns if both diagonals have to have every value, like in an X
+    /// sudoku
+    pub fn is_diagonal(&self) -> bool {
+        return self.constraints.diagonal;
+    }
+
+    /// Sets if both diagonals have to have every value, like in an X
+    /// sudoku. They work like rows: a known value is removed from the rest
+    /// of its diagonals, and they're checked for hidden singles, as
+    /// "region 1", from the top left, and "region 2", from the top right
+    ///
+    /// Boards read from text are diagonal if they have a "# diagonal" line.
+    /// Turning it off once the board has been narrowed doesn't bring back
+    /// the values the diagonals ruled out
+    ///
+    /// ```
+    /// use rsudoku::SudokuBoard;
+    ///
+    /// let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+    /// board.set_diagonal(true);
+    /// assert!(board.solve());
+    ///
+    /// let mut diagonal: Vec<usize> = (0..9).map(|i| board.value((i, i)).unwrap()).collect();
+    /// diagonal.sort();
+    /// assert_eq!(diagonal, (1..=9).collect::<Vec<usize>>());
+    /// ```
+    pub fn set_diagonal(&mut self, diagonal: bool) {
+        if diagonal == self.constraints.diagonal {
+            return;
+        }
+
+        Arc::make_mut(&mut self.constraints).set_diagonal(diagonal, &self.geometry);
+
+        // The known spaces need removing from their new peers
+        self.initialized = false;
+    }
+
+    /// Returns if the board is assumed to have one solution
+    pub fn assumes_unique_solution(&self) -> bool {
+        return self.assume_unique_solution;
+    }
+
+    /// Sets if the board is assumed to have one solution, which lets the
+    /// techniques that need it be used, like unique rectangles. It's off by
+    /// default, and those techniques find nothing while it is
+    ///
+    /// If the board does have more than one solution, these techniques can
+    /// remove every solution, so solving fails
+    pub fn set_assume_unique_solution(&mut self, assume_unique_solution: bool) {
+        self.assume_unique_solution = assume_unique_solution;
+    }
+
+    /// Returns the first deduction `technique` can make on the board,
+    /// without applying it
+    ///
+    /// Returns None if the technique finds nothing, or the board is
+    /// unsolvable
+    pub fn find_deduction(&self, technique: Technique) -> Option<Deduction> {
+        let mut board = self.clone();
+
+        if !board.initialized && board.initial_check().is_err() {
+            return None;
+        }
+
+        return techniques::find(&board, technique);
+    }
+
+    /// Fills in the placements and removes the eliminations of a
+    /// deduction, along with any spaces that become known as a result
+    ///
+    /// Returns an error if the deduction makes the sudoku unsolvable, or
+    /// `OffBoard` or `ValueNotPossible` if it doesn't fit on the board. The
+    /// board isn't changed if it doesn't fit
+    pub fn apply_deduction(&mut self, deduction: &Deduction) -> Result<(), SudokuError> {
+        // Deductions can be made by hand, so check they fit before changing
+        // anything
+        let size = self.geometry.size();
+
+        for &(point, value) in deduction.placements.iter().chain(&deduction.eliminations) {
+            if !self.geometry.contains(point) {
+                return Result::Err(SudokuError::OffBoard { point });
+            }
+
+            if value == 0 || value > size {
+                return Result::Err(SudokuError::ValueNotPossible { point, value });
+            }
+        }
+
+        if !self.initialized {
+            self.initial_check()?;
+        }
+
+        for &(point, value) in &deduction.placements {
+            self.fill_space(self.geometry.index_of(point), value)?;
+        }
+
+        for &(point, value) in &deduction.eliminations {
+            let index = self.geometry.index_of(point);
+
+            if let SudokuValueResult::ValueNowKnown = self.remove_possible_value(index, value)? {
+                self.empty_spaces -= 1;
+
+                let mut known_spaces_to_check = KnownSpaceQueue::new();
+                known_spaces_to_check.push(index);
+
+                self.propagate(&mut known_spaces_to_check)?;
+            }
+        }
+
+        self.apply_constraints()?;
+
+        return Result::Ok(());
+    }
+
+    /// Returns the value of the space at (row, column), or None if it isn't
+    /// known
+    ///
+    /// Panics if the space isn't on the board
+    pub fn value(&self, point: (usize, usize)) -> Option<usize> {
+        match self.get_space(self.index_on_board(point)) {
+            SudokuValue::Known(value) => return Some(*value as usize),
+            SudokuValue::Unknown(_) => return None,
+        }
+    }
+
+    /// Returns the values the space at (row, column) could be, smallest
+    /// first. A known space can only be its value
+    ///
+    /// Panics if the space isn't on the board
+    pub fn candidates(&self, point: (usize, usize)) -> Vec<usize> {
+        match self.get_space(self.index_on_board(point)) {
+            SudokuValue::Known(value) => return vec![*value as usize],
+            SudokuValue::Unknown(possible_values) => return possible_values.iter().collect(),
+        }
+    }
+
+    /// Returns the index of the space at (row, column). Panics if it isn't
+    /// on the board, rather than wrapping round to the next row
+    fn index_on_board(&self, point: (usize, usize)) -> usize {
+        if !self.geometry.contains(point) {
+            panic!("{}", SudokuError::OffBoard { point });
+        }
+
+        return self.geometry.index_of(point);
     }
 
     /// Returns a reference to a space
-    fn get_space(&self, point: (usize, usize)) -> &SudokuValue {
-        return &self.spaces[point.0][point.1];
+    fn get_space(&self, index: usize) -> &SudokuValue {
+        return &self.spaces[index];
+    }
+
+    /// Returns the possible values of a space. Known spaces have none
+    fn possible_values(&self, index: usize) -> Candidates {
+        match self.get_space(index) {
+            SudokuValue::Known(_) => return Candidates::default(),
+            SudokuValue::Unknown(possible_values) => return *possible_values,
+        }
+    }
+
+    /// Returns a mutable reference to a space
+    fn get_space_mut(&mut self, index: usize) -> &mut SudokuValue {
+        return &mut self.spaces[index];
     }
 
     /// Returns the guess with the most impact, which is the guess that
@@ -490,10 +1602,10 @@ impl SudokuBoard {
     ///
     /// If there is a tie, pick the guess with the most possible value
     /// removed from adjacent spaces as a result of the guess
-    fn most_impactful_guess(&self) -> ((usize, usize), usize) {
+    fn most_impactful_guess(&self) -> (usize, usize) {
         #[derive(Debug)]
         struct Impact {
-            point: (usize, usize),
+            index: usize,
             guess: usize,
             number_of_possible_values: usize,
             spaces_solved: usize,
@@ -501,86 +1613,82 @@ impl SudokuBoard {
         }
 
         let mut best_guess = Impact {
-            point: (0, 0),
+            index: 0,
             guess: 0,
             number_of_possible_values: usize::MAX,
             spaces_solved: 0,
             possible_values_removed: 0,
         };
 
-        for i in 0..9 {
-            for j in 0..9 {
-                if let SudokuValue::Unknown(possible_values) = &self.get_space((i, j)) {
-                    if possible_values.len() > best_guess.number_of_possible_values {
-                        continue;
-                    }
-
-                    for possible_value in possible_values {
-                        // Check adjacent values
-                        let mut other_spaces_solved_by_guess = 0;
-                        let mut possible_values_removed_by_guess = 0;
-
-                        // Look at all spaces adjacent to the guess
-                        for point in SudokuBoard::get_adjecent_spaces((i, j)) {
-                            if let SudokuValue::Unknown(adjacent_possible_values) =
-                                self.get_space(point)
-                            {
-                                // Is our guess one of the adjacent spaces possible values?
-                                if adjacent_possible_values.contains(possible_value) {
-                                    possible_values_removed_by_guess += 1;
-                                    if adjacent_possible_values.len() == 2 {
-                                        other_spaces_solved_by_guess += 1;
-                                    }
-                                }
-                            }
-                        }
+        for (index, space) in self.spaces.iter().enumerate() {
+            if let SudokuValue::Unknown(possible_values) = space {
+                if possible_values.len() > best_guess.number_of_possible_values {
+                    continue;
+                }
 
-                        let new_guess = Impact {
-                            point: (i, j),
-                            guess: *possible_value,
-                            number_of_possible_values: possible_values.len(),
-                            spaces_solved: other_spaces_solved_by_guess,
-                            possible_values_removed: possible_values_removed_by_guess,
-                        };
+                for possible_value in possible_values.iter() {
+                    // Check adjacent values
+                    let mut other_spaces_solved_by_guess = 0;
+                    let mut possible_values_removed_by_guess = 0;
 
-                        if new_guess.number_of_possible_values
-                            < best_guess.number_of_possible_values
+                    // Look at all spaces adjacent to the guess
+                    for &adjacent_index in self.constraints.peers(&self.geometry, index) {
+                        if let SudokuValue::Unknown(adjacent_possible_values) =
+                            self.get_space(adjacent_index)
                         {
-                            // Number of possible values for this point is
-                            // less then best guess, so better, because
-                            // the guess is more likely to be correct
-                            best_guess = new_guess;
-                            continue;
+                            // Is our guess one of the adjacent spaces possible values?
+                            if adjacent_possible_values.contains(possible_value) {
+                                possible_values_removed_by_guess += 1;
+                                if adjacent_possible_values.len() == 2 {
+                                    other_spaces_solved_by_guess += 1;
+                                }
+                            }
                         }
+                    }
 
-                        match new_guess.spaces_solved.cmp(&best_guess.spaces_solved) {
-                            Ordering::Greater => {
-                                // New guess solves more spaces then best case, so is better
-
-                                best_guess = new_guess;
+                    let new_guess = Impact {
+                        index,
+                        guess: possible_value,
+                        number_of_possible_values: possible_values.len(),
+                        spaces_solved: other_spaces_solved_by_guess,
+                        possible_values_removed: possible_values_removed_by_guess,
+                    };
+
+                    if new_guess.number_of_possible_values < best_guess.number_of_possible_values {
+                        // Number of possible values for this space is
+                        // less then best guess, so better, because
+                        // the guess is more likely to be correct
+                        best_guess = new_guess;
+                        continue;
+                    }
 
-                                continue;
-                            }
-                            Ordering::Less => continue, // Best guess solves more spaces then new guess
-                            _ => {}
-                        }
+                    match new_guess.spaces_solved.cmp(&best_guess.spaces_solved) {
+                        Ordering::Greater => {
+                            // New guess solves more spaces then best case, so is better
 
-                        if new_guess
-                            .possible_values_removed
-                            .cmp(&best_guess.possible_values_removed)
-                            == Ordering::Greater
-                        {
-                            // New guess removes more possible values then old guess
                             best_guess = new_guess;
 
                             continue;
                         }
+                        Ordering::Less => continue, // Best guess solves more spaces then new guess
+                        _ => {}
+                    }
+
+                    if new_guess
+                        .possible_values_removed
+                        .cmp(&best_guess.possible_values_removed)
+                        == Ordering::Greater
+                    {
+                        // New guess removes more possible values then old guess
+                        best_guess = new_guess;
+
+                        continue;
                     }
                 }
             }
         }
 
-        return (best_guess.point, best_guess.guess);
+        return (best_guess.index, best_guess.guess);
     }
 
     /// Tries to solve the sudoku
@@ -590,108 +1698,363 @@ impl SudokuBoard {
     ///
     /// Returns if the sudoku was solved
     pub fn solve(&mut self) -> bool {
+        return self.try_solve().is_ok();
+    }
+
+    /// Tries to solve the sudoku, the same as `solve`
+    ///
+    /// Returns why the sudoku is unsolvable if it couldn't be solved. If
+    /// every guess was wrong, this is the problem found after removing the
+    /// last one
+    pub fn try_solve(&mut self) -> Result<(), SudokuError> {
         // Preform some quick checks to fill in easy values and remove
         // possible values for each space
 
-        if !self.initialized && self.initial_check().is_err() {
-            return false;
-        }
-
-        if self.is_solved() {
-            return true;
+        if !self.initialized {
+            self.initial_check()?;
         }
 
-        if self.narrow_full().is_err() {
-            return false;
-        }
         if self.is_solved() {
-            return true;
+            return Result::Ok(());
         }
 
         loop {
-            if self.narrow_full().is_err() {
-                return false;
-            }
-            if self.is_solved() {
-                return true;
+            if self.narrow_full()? {
+                return Result::Ok(());
             }
 
             // Get most impactful guess
-            let (point, guess_value) = self.most_impactful_guess();
+            let (index, guess_value) = self.most_impactful_guess();
 
             let mut guess_board = self.clone();
 
-            if guess_board.fill_space(point, guess_value).is_ok() {
+            if guess_board.fill_space(index, guess_value).is_ok() {
                 // Try to solve the board with a guess
                 if guess_board.solve() {
                     // Set this board to the guess board if guess board was solved
                     *self = guess_board;
-                    return true;
+                    return Result::Ok(());
                 }
             }
 
             // The guess didn't create a solvable board; remove it
-            let result_of_removal = self.spaces[point.0][point.1].remove(guess_value);
+            self.remove_guess(index, guess_value)?;
 
-            // Remove was invalid
-            if result_of_removal.is_err() {
-                return false;
+            if self.is_solved() {
+                return Result::Ok(());
             }
+        }
+    }
 
-            if let SudokuValueResult::ValueNowKnown =
-                result_of_removal.expect("Checked for error above")
-            {
-                // The guess was wrong, but now the guessed space is known
-                self.empty_spaces -= 1;
+    /// Removes a guess from the possible values of a space, once it's
+    /// known to be wrong, or every solution with it has been looked at
+    ///
+    /// If the space is now known, fills it in. Returns an error if the
+    /// sudoku is now unsolvable
+    fn remove_guess(&mut self, index: usize, guess_value: usize) -> Result<(), SudokuError> {
+        let result_of_removal = self.remove_possible_value(index, guess_value)?;
 
-                if let SudokuValue::Known(point_new_value) = self.get_space(point) {
-                    if self.fill_space(point, *point_new_value).is_err() {
-                        // Filling in the space resulted in an unsolvable sudoku
-                        return false;
-                    }
+        if let SudokuValueResult::ValueNowKnown = result_of_removal {
+            // The guess was wrong, but now the guessed space is known.
+            // Removing its value may result in an unsolvable sudoku
+            self.empty_spaces -= 1;
 
-                    if self.is_solved() {
-                        return true;
-                    }
-                }
-            };
+            let mut known_spaces_to_check = KnownSpaceQueue::new();
+            known_spaces_to_check.push(index);
+
+            self.propagate(&mut known_spaces_to_check)?;
+        };
+
+        self.apply_constraints()?;
+
+        return Result::Ok(());
+    }
+
+    /// Returns an iterator over every solution of the sudoku
+    ///
+    /// Solutions are found lazily, with the same narrowing and guessing as
+    /// `solve`, so the iterator can be stopped at any time. Each solution is
+    /// only returned once. The board isn't changed
+    ///
+    /// The board isn't assumed to have one solution while they're found,
+    /// even if `set_assume_unique_solution` is on, as unique rectangles and
+    /// BUG+1 would remove all but one of them
+    ///
+    /// ```
+    /// use rsudoku::SudokuBoard;
+    ///
+    /// let blank: SudokuBoard = "0".repeat(81).parse().unwrap();
+    ///
+    /// for solution in blank.solutions().take(3) {
+    ///     assert!(solution.is_solved());
+    /// }
+    /// ```
+    pub fn solutions(&self) -> Solutions {
+        let mut board = self.clone();
+        board.assume_unique_solution = false;
+
+        return Solutions {
+            boards: vec![board],
+        };
+    }
+
+    /// Counts the solutions of the sudoku, stopping once `limit` have been
+    /// found
+    ///
+    /// A limit of 0 still looks for one solution, so it's `AtLeast(0)` if
+    /// the sudoku has one, and `Exactly(0)` if it doesn't
+    ///
+    /// This uses the same narrowing and guessing as `solve`, but keeps
+    /// guessing after a solution is found. The board isn't changed
+    ///
+    /// ```
+    /// use rsudoku::{SolutionCount, SudokuBoard};
+    ///
+    /// let blank: SudokuBoard = "0".repeat(81).parse().unwrap();
+    /// assert_eq!(blank.count_solutions(10), SolutionCount::AtLeast(10));
+    /// ```
+    pub fn count_solutions(&self, limit: usize) -> SolutionCount {
+        let solutions_found = self.solutions().take(limit.max(1)).count();
+
+        if solutions_found >= limit && solutions_found > 0 {
+            return SolutionCount::AtLeast(limit);
+        }
+
+        return SolutionCount::Exactly(solutions_found);
+    }
+
+    /// Returns if the sudoku has exactly one solution
+    pub fn has_unique_solution(&self) -> bool {
+        return self.count_solutions(2) == SolutionCount::Exactly(1);
+    }
+}
+
+/// An iterator over the solutions of a sudoku. Made by
+/// `SudokuBoard::solutions`
+///
+/// Boards that still need looking at are kept on a stack. The top board
+/// is narrowed, and is either returned if solved, dropped if unsolvable,
+/// or split into two boards on the most impactful guess: one with the guess
+/// filled in, and one with the guess removed. No solution can be on both
+/// boards, so none are repeated
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    boards: Vec<SudokuBoard>,
+}
+
+impl Iterator for Solutions {
+    type Item = SudokuBoard;
+
+    fn next(&mut self) -> Option<SudokuBoard> {
+        while let Some(mut board) = self.boards.pop() {
+            if !board.initialized && board.initial_check().is_err() {
+                continue;
+            }
+
+            match board.narrow_full() {
+                Result::Err(_) => continue,
+                Result::Ok(true) => return Some(board),
+                Result::Ok(false) => {}
+            }
+
+            let (index, guess_value) = board.most_impactful_guess();
+
+            let mut guess_board = board.clone();
+
+            // Pushed first, so it's looked at after all the solutions with
+            // the guess
+            if board.remove_guess(index, guess_value).is_ok() {
+                self.boards.push(board);
+            }
+
+            if guess_board.fill_space(index, guess_value).is_ok() {
+                self.boards.push(guess_board);
+            }
+        }
+
+        return None;
+    }
+}
+
+impl FromStr for SudokuBoard {
+    type Err = SudokuError;
+
+    /// Parse a sudoku board from text
+    ///
+    /// Every character that `SudokuValue::from` accepts is a space, read
+    /// row by row: '1' to '9' are known values, and '0' or any letter is an
+    /// empty space. Whitespace and the lines printed between boxes ('|',
+    /// '-' and '+') are skipped. Any other character is an error, unless it
+    /// comes after the 81st space, as everything after that is ignored
+    ///
+    /// A line of "# diagonal" before the last space makes the board
+    /// diagonal, like an X sudoku. See `SudokuBoard::set_diagonal`
+    ///
+    /// This reads 9x9 boards. Other shapes are read the same way by
+    /// `SudokuBoard::from_str_with_geometry`, except boards with more than 9
+    /// values, where each space is a number from 0 to the size of the
+    /// board, or a letter, separated by whitespace. Boards using other
+    /// symbols are read by `SudokuBoard::from_str_with_symbols`, with a
+    /// character for each space if every symbol is one character
+    ///
+    /// ```
+    /// use rsudoku::SudokuBoard;
+    ///
+    /// let board: SudokuBoard = "
+    ///     000 000 010
+    ///     000 002 003
+    ///     000 400 000
+    ///
+    ///     000 000 500
+    ///     401 600 000
+    ///     007 100 000
+    ///
+    ///     050 000 200
+    ///     000 080 040
+    ///     030 910 000"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert!(!board.is_solved());
+    /// ```
+    fn from_str(board_string: &str) -> Result<Self, SudokuError> {
+        return SudokuBoard::parse(board_string, Geometry::standard(), Symbols::standard());
+    }
+}
+
+/// Splits a line of a board file into the text of each space, along with
+/// the column it starts at. Boards with single character symbols have a
+/// character for each space, and others have symbols separated by
+/// whitespace
+fn tokens(line: &str, single_characters: bool) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+
+    // The column and byte the current number started at
+    let mut start = None;
+
+    for (column, (byte_index, character)) in line.char_indices().enumerate() {
+        if single_characters {
+            tokens.push((column, &line[byte_index..byte_index + character.len_utf8()]));
+        } else if character.is_whitespace() {
+            if let Some((start_column, start_byte)) = start.take() {
+                tokens.push((start_column, &line[start_byte..byte_index]));
+            }
+        } else if start.is_none() {
+            start = Some((column, byte_index));
         }
     }
+
+    if let Some((start_column, start_byte)) = start {
+        tokens.push((start_column, &line[start_byte..]));
+    }
+
+    return tokens;
+}
+
+impl TryFrom<[[u8; 9]; 9]> for SudokuBoard {
+    type Error = SudokuError;
+
+    /// Create a sudoku board from rows of spaces
+    ///
+    /// Each space is 0 for an empty space, or 1 to 9 for a known one. ASCII
+    /// characters are also accepted, with the same rules as parsing a board
+    /// from text, so `b'7'` and `b'0'` work too
+    fn try_from(grid: [[u8; 9]; 9]) -> Result<Self, SudokuError> {
+        return SudokuBoard::from_bytes(grid.iter().flatten(), Geometry::standard());
+    }
+}
+
+impl TryFrom<&[u8; 81]> for SudokuBoard {
+    type Error = SudokuError;
+
+    /// Create a sudoku board from 81 spaces, read row by row
+    ///
+    /// Spaces are read the same way as `TryFrom<[[u8; 9]; 9]>`, so this
+    /// accepts both digits and byte strings
+    ///
+    /// ```
+    /// use rsudoku::SudokuBoard;
+    ///
+    /// let puzzle = b"\
+    ///     000000010\
+    ///     000002003\
+    ///     000400000\
+    ///     000000500\
+    ///     401600000\
+    ///     007100000\
+    ///     050000200\
+    ///     000080040\
+    ///     030910000";
+    ///
+    /// let mut board = SudokuBoard::try_from(puzzle).unwrap();
+    /// assert!(board.solve());
+    /// ```
+    fn try_from(spaces: &[u8; 81]) -> Result<Self, SudokuError> {
+        return SudokuBoard::from_bytes(spaces, Geometry::standard());
+    }
 }
 
 impl fmt::Display for SudokuBoard {
     /// Print the Sudoku board
     ///
-    /// The board is made of the Sudoku Values, separated by spaces, and
-    /// horizontal lines
+    /// The board is made of the symbols of the Sudoku Values, separated by
+    /// spaces, with lines between the boxes. Symbols are padded to the same
+    /// width, like on boards with more than 9 values
+    ///
+    /// Diagonal boards start with a "# diagonal" line, so they're read back
+    /// as diagonal. With `{:#}`, the spaces on the diagonals are also marked
+    /// with '\\' from the top left, '/' from the top right, or '*' for
+    /// both. Boards printed this way can't be read back
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut lines: Vec<String> = Vec::with_capacity(11);
+        let size = self.geometry.size();
+        let width = self.symbols.width(size);
+        let mark_diagonals = self.is_diagonal() && f.alternate();
+
+        let mut lines: Vec<String> = Vec::with_capacity(size * 2 + 1);
+
+        if self.is_diagonal() {
+            lines.push(String::from("# diagonal"));
+        }
 
-        let mut line_index = 0;
-        for line in &self.spaces {
+        for (row_index, row) in self.spaces.chunks(size).enumerate() {
             // Create a line containing sudoku values
-            lines.push(String::with_capacity(21));
+            let mut line = String::with_capacity(size * (width + 1) * 2);
+
+            for (space_index, space) in row.iter().enumerate() {
+                if mark_diagonals {
+                    let main = space_index == row_index;
+                    let anti = space_index == size - 1 - row_index;
+
+                    line.push(match (main, anti) {
+                        (true, true) => '*',
+                        (true, false) => '\\',
+                        (false, true) => '/',
+                        (false, false) => ' ',
+                    });
+                }
 
-            for (space_index, space) in line.iter().enumerate() {
-                if space_index == 8 {
-                    lines[line_index].push_str(&space.to_string());
-                } else {
-                    lines[line_index].push_str(&format!("{} ", space));
+                line.push_str(&format!(
+                    "{:>width$}",
+                    space.symbol(&self.symbols),
+                    width = width
+                ));
+
+                if space_index < size - 1 {
+                    line.push(' ');
 
-                    // Add a horizontal line character to make boxes
-                    if (space_index + 1) % 3 == 0 {
-                        lines[line_index].push_str("| ")
+                    // Add a vertical line character to make boxes
+                    if (space_index + 1) % self.geometry.box_width() == 0 {
+                        line.push_str("| ");
                     }
                 }
             }
 
-            // Insert two horizontal lines
-            if line_index == 2 || line_index == 6 {
-                lines.push(String::from("---------------------"));
-                line_index += 1
+            // Insert a horizontal line between boxes
+            if row_index > 0 && row_index % self.geometry.box_height() == 0 {
+                lines.push("-".repeat(line.chars().count()));
             }
 
-            line_index += 1;
+            lines.push(line);
         }
 
         for (line_index, line) in lines.iter().enumerate() {
@@ -714,11 +2077,11 @@ impl fmt::Display for SudokuBoard {
 ///
 /// param: board_name - filename of board in the `boards` dir
 ///
-/// Returns IO error if board can't be read.
-pub fn solve(board_path: &str) -> io::Result<SudokuBoard> {
-    let mut board = SudokuBoard::new(board_path)?;
+/// Returns an error if board can't be read or parsed.
+pub fn solve(board_path: &str) -> Result<SudokuBoard, SudokuError> {
+    let mut board = SudokuBoard::from_file(board_path)?;
 
     board.solve();
 
-    return io::Result::Ok(board);
+    return Result::Ok(board);
 }
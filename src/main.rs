@@ -3,7 +3,7 @@
 
 use std::{env, fs, io, time};
 
-use rsudoku::SudokuBoard;
+use rsudoku::{Generator, SudokuBoard, SudokuError};
 
 /// Solve all the sudoku boards in the `boards` dir
 fn time_all_boards() -> io::Result<()> {
@@ -24,8 +24,8 @@ fn time_all_boards() -> io::Result<()> {
             let start = time::Instant::now();
             let board = rsudoku::solve(dir_entry.path().to_str().unwrap());
 
-            if board.is_ok() {
-                let solved = board.unwrap().is_solved();
+            if let Result::Ok(board) = &board {
+                let solved = board.is_solved();
 
                 let time_taken = start.elapsed();
 
@@ -43,7 +43,7 @@ fn time_all_boards() -> io::Result<()> {
                 );
             } else {
                 eprintln!(
-                    "Couldn't read {} due to {:?}",
+                    "Couldn't read {} due to {}",
                     dir_entry.file_name().into_string().unwrap(),
                     board.err().unwrap()
                 )
@@ -63,16 +63,21 @@ fn time_all_boards() -> io::Result<()> {
 fn time_solve(board_path: &str) -> io::Result<()> {
     let start = time::Instant::now();
 
-    let board_result: Result<SudokuBoard, io::Error> = rsudoku::solve(board_path);
+    let board_result: Result<SudokuBoard, SudokuError> = rsudoku::solve(board_path);
     if board_result.is_err() {
         let error = board_result.err().unwrap();
-        match error.kind() {
-            io::ErrorKind::NotFound => {
-                eprintln!("Can't find board at {board_path}");
-                return io::Result::Ok(());
+        match error {
+            SudokuError::Io(error) => {
+                if error.kind() == io::ErrorKind::NotFound {
+                    eprintln!("Can't find board at {board_path}");
+                    return io::Result::Ok(());
+                }
+
+                return io::Result::Err(error);
             }
             _ => {
-                return io::Result::Err(error);
+                eprintln!("Can't read board at {board_path}: {error}");
+                return io::Result::Ok(());
             }
         }
     }
@@ -98,6 +103,72 @@ fn time_solve(board_path: &str) -> io::Result<()> {
     return io::Result::Ok(());
 }
 
+/// Load a board, and print each step taken to solve it, then the solution
+fn print_trace(board_path: &str) -> io::Result<()> {
+    let mut board = match SudokuBoard::from_file(board_path) {
+        Result::Ok(board) => board,
+        Result::Err(SudokuError::Io(error)) => return io::Result::Err(error),
+        Result::Err(error) => {
+            eprintln!("Can't read board at {board_path}: {error}");
+            return io::Result::Ok(());
+        }
+    };
+
+    match board.solve_with_trace() {
+        Result::Ok(trace) => {
+            print!("{}", trace);
+            println!("{}", board);
+        }
+        Result::Err(error) => println!("No solutions exist: {error}"),
+    }
+
+    return io::Result::Ok(());
+}
+
+/// Load a board, and print how hard it is
+fn print_rating(board_path: &str) -> io::Result<()> {
+    let board = match SudokuBoard::from_file(board_path) {
+        Result::Ok(board) => board,
+        Result::Err(SudokuError::Io(error)) => return io::Result::Err(error),
+        Result::Err(error) => {
+            eprintln!("Can't read board at {board_path}: {error}");
+            return io::Result::Ok(());
+        }
+    };
+
+    match board.rate() {
+        Result::Ok(rating) => println!("{}", rating),
+        Result::Err(error) => println!("No solutions exist: {error}"),
+    }
+
+    return io::Result::Ok(());
+}
+
+/// Make a new sudoku from a seed, and print it and its solution
+fn print_generated(seed: &str) -> io::Result<()> {
+    let seed: u64 = match seed.parse() {
+        Result::Ok(seed) => seed,
+        Result::Err(_) => {
+            eprintln!("Seed must be a number, not {seed}");
+            return io::Result::Ok(());
+        }
+    };
+
+    let puzzle = match Generator::new(seed).generate() {
+        Result::Ok(puzzle) => puzzle,
+        Result::Err(error) => {
+            eprintln!("Couldn't make a sudoku: {error}");
+            return io::Result::Ok(());
+        }
+    };
+
+    println!("{}", puzzle.board);
+    println!();
+    println!("{}", puzzle.solution);
+
+    return io::Result::Ok(());
+}
+
 #[allow(dead_code)]
 fn time_single_board(path: &str) {
     const DELTA: time::Duration = time::Duration::from_secs(300);
@@ -146,6 +217,12 @@ fn main() -> io::Result<()> {
 
     if args.len() == 1 {
         return time_all_boards();
+    } else if args.len() == 3 && args[1] == "--trace" {
+        return print_trace(&args[2]);
+    } else if args.len() == 3 && args[1] == "--rate" {
+        return print_rating(&args[2]);
+    } else if args.len() == 3 && args[1] == "--generate" {
+        return print_generated(&args[2]);
     } else {
         return time_solve(args.get(1).unwrap());
     }
@@ -0,0 +1,243 @@
+//! Taking away givens a sudoku doesn't need
+
+use std::sync::Arc;
+
+use crate::{
+    generator::{board_of, grid_of, Rng},
+    SolutionCount, SudokuBoard, SudokuError, Technique,
+};
+
+/// The order `SudokuBoard::minimize` tries taking away givens in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemovalOrder {
+    /// Row by row, starting at the top left
+    RowByRow,
+
+    /// A random order, from a seed. The same seed always gives the same
+    /// order
+    Random(u64),
+
+    /// These spaces, in order, as (row, column). Givens that aren't listed
+    /// are kept
+    Custom(Vec<(usize, usize)>),
+}
+
+impl SudokuBoard {
+    /// Takes away givens one at a time, in `order`, putting each back if
+    /// the sudoku would have more than one solution. Every given left is
+    /// needed, unless `RemovalOrder::Custom` leaves some out
+    ///
+    /// The known spaces are the givens, so this should be used before the
+    /// board is solved. The board's symbols, constraints, and settings are
+    /// kept
+    ///
+    /// Returns `MultipleSolutions` if the sudoku doesn't have one solution
+    /// to start with, or why it's unsolvable
+    ///
+    /// ```
+    /// use rsudoku::{RemovalOrder, SudokuBoard};
+    ///
+    /// let board = SudokuBoard::from_file("boards/easy").unwrap();
+    /// assert!(!board.is_minimal());
+    ///
+    /// let minimal = board.minimize(RemovalOrder::Random(3)).unwrap();
+    /// assert!(minimal.is_minimal());
+    /// assert!(minimal.redundant_givens().is_empty());
+    /// ```
+    pub fn minimize(&self, order: RemovalOrder) -> Result<SudokuBoard, SudokuError> {
+        self.check_unique_solution()?;
+
+        let mut grid = grid_of(self);
+        let size = self.geometry.size();
+
+        let order: Vec<usize> = match order {
+            RemovalOrder::RowByRow => (0..grid.len()).collect(),
+            RemovalOrder::Random(seed) => {
+                let mut order: Vec<usize> = (0..grid.len()).collect();
+                Rng::new(seed).shuffle(&mut order);
+                order
+            }
+            RemovalOrder::Custom(points) => points
+                .into_iter()
+                .filter(|&(row, column)| row < size && column < size)
+                .map(|point| self.geometry.index_of(point))
+                .collect(),
+        };
+
+        for index in order {
+            let given = grid[index];
+            if given == 0 {
+                continue;
+            }
+
+            grid[index] = 0;
+
+            if !self.checking_board(&grid).has_unique_solution() {
+                grid[index] = given;
+            }
+        }
+
+        return Result::Ok(self.with_givens(&grid));
+    }
+
+    /// Returns if the sudoku has one solution, and every given is needed
+    /// for that
+    pub fn is_minimal(&self) -> bool {
+        return self.checking_board(&grid_of(self)).has_unique_solution()
+            && self.redundant_givens().is_empty();
+    }
+
+    /// Returns the givens that could be taken away on their own, without
+    /// the sudoku having more than one solution, as (row, column)
+    ///
+    /// Taking one away can make others needed, so they can't always all be
+    /// taken away. Sudoku's without one solution have no redundant givens
+    pub fn redundant_givens(&self) -> Vec<(usize, usize)> {
+        let mut grid = grid_of(self);
+        let mut redundant = Vec::new();
+
+        if !self.checking_board(&grid).has_unique_solution() {
+            return redundant;
+        }
+
+        for index in 0..grid.len() {
+            let given = grid[index];
+            if given == 0 {
+                continue;
+            }
+
+            grid[index] = 0;
+
+            if self.checking_board(&grid).has_unique_solution() {
+                redundant.push(self.geometry.point_of(index));
+            }
+
+            grid[index] = given;
+        }
+
+        return redundant;
+    }
+
+    /// Returns an error if the givens don't have exactly one solution
+    fn check_unique_solution(&self) -> Result<(), SudokuError> {
+        let board = self.checking_board(&grid_of(self));
+
+        match board.count_solutions(2) {
+            SolutionCount::Exactly(1) => return Result::Ok(()),
+            SolutionCount::Exactly(0) => {
+                let mut unsolvable = board;
+                return unsolvable.try_solve();
+            }
+            _ => return Result::Err(SudokuError::MultipleSolutions),
+        }
+    }
+
+    /// Makes a board with the givens in `grid`, and the same shape,
+    /// symbols, constraints, and settings as this one
+    fn with_givens(&self, grid: &[u8]) -> SudokuBoard {
+        let mut board = board_of(grid, &self.geometry);
+        board.symbols = Arc::clone(&self.symbols);
+        board.constraints = Arc::clone(&self.constraints);
+        board.techniques = self.techniques;
+        board.assume_unique_solution = self.assume_unique_solution;
+
+        return board;
+    }
+
+    /// Makes a board with the givens in `grid` to count the solutions of.
+    /// It isn't assumed to have one solution, and can't use the techniques
+    /// that need that, as they'd hide the other solutions
+    fn checking_board(&self, grid: &[u8]) -> SudokuBoard {
+        let mut board = self.with_givens(grid);
+        board.assume_unique_solution = false;
+
+        for technique in Technique::ALL {
+            if technique.needs_unique_solution() {
+                board.techniques.remove(technique);
+            }
+        }
+
+        return board;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, RemovalOrder, SolutionCount, SudokuBoard, Technique, TechniqueSet};
+
+    /// Reads a bundled board that assumes it has one solution, and can use
+    /// the techniques that need that
+    fn assuming_board(name: &str) -> SudokuBoard {
+        let mut board = SudokuBoard::from_file(format!("boards/{}", name)).unwrap();
+        let mut techniques = TechniqueSet::default();
+
+        for technique in Technique::ALL {
+            if technique.needs_unique_solution() {
+                techniques.insert(technique);
+            }
+        }
+
+        board.set_techniques(techniques).unwrap();
+        board.set_assume_unique_solution(true);
+
+        return board;
+    }
+
+    /// Counts the solutions of the board's givens, without any techniques
+    fn solution_count(board: &SudokuBoard) -> SolutionCount {
+        let mut board = board.clone();
+        board.set_techniques(TechniqueSet::empty()).unwrap();
+        board.set_assume_unique_solution(false);
+
+        return board.count_solutions(2);
+    }
+
+    #[test]
+    fn minimize_leaves_one_solution() {
+        for seed in 0..5 {
+            let board = assuming_board("hard");
+            let minimal = board.minimize(RemovalOrder::Random(seed)).unwrap();
+
+            assert_eq!(solution_count(&minimal), SolutionCount::Exactly(1));
+            assert!(minimal.assumes_unique_solution());
+            assert_eq!(minimal.techniques(), board.techniques());
+        }
+    }
+
+    #[test]
+    fn minimal_boards_have_no_redundant_givens() {
+        let minimal = assuming_board("easy")
+            .minimize(RemovalOrder::RowByRow)
+            .unwrap();
+
+        assert!(minimal.is_minimal());
+        assert!(minimal.redundant_givens().is_empty());
+    }
+
+    #[test]
+    fn redundant_givens_can_each_be_taken_away() {
+        let board = assuming_board("easy");
+        let redundant = board.redundant_givens();
+        assert!(!redundant.is_empty());
+        assert!(!board.is_minimal());
+
+        for (row, column) in redundant {
+            let mut grid: Vec<u8> = (0..81)
+                .map(|index| board.value((index / 9, index % 9)).unwrap_or(0) as u8)
+                .collect();
+            grid[row * 9 + column] = 0;
+
+            let fewer = SudokuBoard::from_grid(&grid, Geometry::new(3, 3).unwrap()).unwrap();
+            assert_eq!(solution_count(&fewer), SolutionCount::Exactly(1));
+        }
+    }
+
+    #[test]
+    fn boards_without_one_solution_are_never_minimal() {
+        let board = assuming_board("unsolveable");
+
+        assert!(!board.is_minimal());
+        assert!(board.redundant_givens().is_empty());
+        assert!(board.minimize(RemovalOrder::RowByRow).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Rating how hard a sudoku is
+
+use std::fmt;
+
+use crate::{techniques, StepKind, SudokuBoard, SudokuError, Technique, TechniqueSet};
+
+/// How hard a sudoku is. Made by `SudokuBoard::rate`
+///
+/// ```
+/// use rsudoku::{Level, SudokuBoard};
+///
+/// let mut last_score = 0.0;
+///
+/// for name in ["easy", "medium", "hard", "veryhard"] {
+///     let board = SudokuBoard::from_file(format!("boards/{name}")).unwrap();
+///     let rating = board.rate().unwrap();
+///
+///     assert!(rating.score > last_score);
+///     last_score = rating.score;
+/// }
+///
+/// let easy = SudokuBoard::from_file("boards/easy").unwrap();
+/// assert_eq!(easy.rate().unwrap().level, Level::Easy);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    /// The hardest step needed to solve the sudoku without guessing, using
+    /// every technique
+    pub hardest: HardestStep,
+
+    /// The score of the hardest step, like Sudoku Explainer's ratings
+    pub score: f32,
+
+    /// How many guesses `solve` made, with the board's own techniques
+    pub guesses: usize,
+
+    /// How many of those guesses turned out to be wrong
+    pub backtracks: usize,
+
+    pub level: Level,
+}
+
+/// The hardest kind of step a solve needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HardestStep {
+    /// A space with one possible value left
+    NakedSingle,
+
+    /// A space that's the only one in a unit that can be a value
+    HiddenSingle,
+
+    Technique(Technique),
+
+    /// None of the techniques could solve the sudoku
+    Guess,
+}
+
+/// A rough grade of how hard a sudoku is, from its score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Only naked singles
+    Easy,
+
+    /// Hidden singles
+    Medium,
+
+    /// Locked candidates, and the subsets and fish up to triples
+    Hard,
+
+    /// Wings, coloring, chains, quads, and the other techniques
+    Expert,
+
+    /// Needs guessing
+    Extreme,
+}
+
+impl HardestStep {
+    /// Returns how hard the step is, like `Technique::score`
+    ///
+    /// Naked singles are the easiest here, unlike in Sudoku Explainer, since
+    /// the solver fills them in as soon as they show up. Guessing scores
+    /// more than any technique
+    pub fn score(self) -> f32 {
+        match self {
+            HardestStep::NakedSingle => 1.0,
+            HardestStep::HiddenSingle => 1.5,
+            HardestStep::Technique(technique) => technique.score(),
+            HardestStep::Guess => 9.0,
+        }
+    }
+}
+
+impl fmt::Display for HardestStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HardestStep::NakedSingle => write!(f, "Naked Single"),
+            HardestStep::HiddenSingle => write!(f, "Hidden Single"),
+            HardestStep::Technique(technique) => write!(f, "{}", technique),
+            HardestStep::Guess => write!(f, "Guessing"),
+        }
+    }
+}
+
+impl Level {
+    /// Returns the level a score falls in
+    pub fn from_score(score: f32) -> Level {
+        if score <= HardestStep::NakedSingle.score() {
+            return Level::Easy;
+        } else if score <= HardestStep::HiddenSingle.score() {
+            return Level::Medium;
+        } else if score <= Technique::HiddenTriple.score() {
+            return Level::Hard;
+        } else if score < HardestStep::Guess.score() {
+            return Level::Expert;
+        } else {
+            return Level::Extreme;
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Easy => write!(f, "easy"),
+            Level::Medium => write!(f, "medium"),
+            Level::Hard => write!(f, "hard"),
+            Level::Expert => write!(f, "expert"),
+            Level::Extreme => write!(f, "extreme"),
+        }
+    }
+}
+
+impl fmt::Display for Rating {
+    /// Prints the rating like "hard (2.6, Pointing), 0 guesses, 0 backtracks"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:.1}, {}), {} guesses, {} backtracks",
+            self.level, self.score, self.hardest, self.guesses, self.backtracks
+        )
+    }
+}
+
+impl SudokuBoard {
+    /// Rates how hard the sudoku is. It's solved once without guessing,
+    /// always taking the easiest step it can, to find the hardest step
+    /// needed. Then it's solved like `solve` to count the guesses
+    ///
+    /// Techniques that need one solution are only used if the board assumes
+    /// it has one. The board isn't changed
+    ///
+    /// Returns an error if the sudoku is unsolvable
+    pub fn rate(&self) -> Result<Rating, SudokuError> {
+        let hardest = self.hardest_step()?;
+
+        let mut board = self.clone();
+        let trace = board.solve_with_trace()?;
+
+        let count_steps = |matches: fn(&StepKind) -> bool| {
+            return trace
+                .steps
+                .iter()
+                .filter(|step| matches(&step.kind))
+                .count();
+        };
+
+        return Result::Ok(Rating {
+            hardest,
+            score: hardest.score(),
+            guesses: count_steps(|kind| matches!(kind, StepKind::Guess { .. })),
+            backtracks: count_steps(|kind| matches!(kind, StepKind::Backtrack { .. })),
+            level: Level::from_score(hardest.score()),
+        });
+    }
+
+    /// Solves a copy of the board with every technique that works on it,
+    /// but no guessing, and returns the hardest step it took
+    fn hardest_step(&self) -> Result<HardestStep, SudokuError> {
+        let mut board = self.clone();
+        board.techniques = TechniqueSet::all()
+            .iter()
+            .filter(|technique| technique.works_on(&board.geometry))
+            .collect();
+
+        let mut hardest = HardestStep::NakedSingle;
+
+        if !board.initialized {
+            board.initial_check()?;
+        }
+
+        while !board.is_solved() {
+            if let Some((_, index, value)) = board.find_hidden_single()? {
+                hardest = hardest.max(HardestStep::HiddenSingle);
+                board.fill_space(index, value)?;
+                continue;
+            }
+
+            let deduction = board
+                .techniques
+                .iter()
+                .find_map(|technique| techniques::find(&board, technique));
+
+            match deduction {
+                Some(deduction) => {
+                    hardest = hardest.max(HardestStep::Technique(deduction.technique));
+                    board.apply_deduction(&deduction)?;
+                }
+                None => return Result::Ok(HardestStep::Guess),
+            }
+        }
+
+        return Result::Ok(hardest);
+    }
+}
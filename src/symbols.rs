@@ -0,0 +1,252 @@
+//! The symbols values are read and printed with
+
+use std::sync::{Arc, OnceLock};
+
+use crate::{Geometry, SudokuError};
+
+/// The symbol for each value, used to read and print boards, and the
+/// symbol for an empty space
+///
+/// The first symbol is value 1, the next is value 2, and so on. A board
+/// only uses as many as it has values, so A to Z works for both 16x16
+/// boards, as A to P, and 25x25 boards, as A to Y
+///
+/// ```
+/// use rsudoku::{Geometry, SudokuBoard, Symbols};
+///
+/// let board = SudokuBoard::from_str_with_symbols(
+///     "
+///     A . | . .
+///     . . | C .
+///     ---------
+///     . D | . .
+///     . . | . B",
+///     Geometry::new(2, 2).unwrap(),
+///     Symbols::letters(),
+/// )
+/// .unwrap();
+///
+/// assert!(board.to_string().starts_with("A . | . ."));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    values: Vec<String>,
+    empty: String,
+}
+
+impl Symbols {
+    /// Makes symbols from a list, with the symbol for value 1 first, and a
+    /// symbol for empty spaces
+    ///
+    /// Returns `InvalidSymbol` for a symbol that's blank, used twice, or
+    /// has whitespace or a character from the lines between boxes ('|',
+    /// '-' and '+') in it
+    pub fn new<S: AsRef<str>>(
+        values: impl IntoIterator<Item = S>,
+        empty: &str,
+    ) -> Result<Symbols, SudokuError> {
+        let values: Vec<String> = values
+            .into_iter()
+            .map(|value| value.as_ref().to_string())
+            .collect();
+        let empty = empty.to_string();
+
+        // Each symbol is checked against the ones before it, and the empty
+        // one against all of the values
+        for (index, symbol) in values.iter().chain([&empty]).enumerate() {
+            let invalid = symbol.is_empty()
+                || symbol.chars().any(Symbols::is_layout_character)
+                || values[..index].contains(symbol);
+
+            if invalid {
+                return Result::Err(SudokuError::InvalidSymbol {
+                    symbol: symbol.clone(),
+                });
+            }
+        }
+
+        return Result::Ok(Symbols { values, empty });
+    }
+
+    /// Makes symbols with a character for each value, like "0123456789ABCDEF"
+    pub fn from_chars(values: &str, empty: char) -> Result<Symbols, SudokuError> {
+        return Symbols::new(
+            values.chars().map(String::from),
+            empty.encode_utf8(&mut [0; 4]),
+        );
+    }
+
+    /// Values as numbers, so 1 to 9, then 10 and up on bigger boards, with
+    /// 'X' for empty spaces. Boards use these unless they're given others
+    pub fn numbers() -> Symbols {
+        return Symbols::new((1..=Geometry::MAX_SIZE).map(|value| value.to_string()), "X").unwrap();
+    }
+
+    /// 0 to 9, then A to F, with '.' for empty spaces. Used for 16x16
+    /// boards
+    pub fn hex() -> Symbols {
+        return Symbols::from_chars("0123456789ABCDEF", '.').unwrap();
+    }
+
+    /// A to Z, with '.' for empty spaces
+    pub fn letters() -> Symbols {
+        return Symbols::from_chars("ABCDEFGHIJKLMNOPQRSTUVWXYZ", '.').unwrap();
+    }
+
+    /// The numbers, which are only made once, and shared
+    pub(crate) fn standard() -> Arc<Symbols> {
+        static STANDARD: OnceLock<Arc<Symbols>> = OnceLock::new();
+
+        return Arc::clone(STANDARD.get_or_init(|| Arc::new(Symbols::numbers())));
+    }
+
+    /// Returns the symbol of each value, starting with value 1
+    pub fn values(&self) -> &[String] {
+        return &self.values;
+    }
+
+    /// Returns the symbol for an empty space
+    pub fn empty(&self) -> &str {
+        return &self.empty;
+    }
+
+    /// Returns the value a symbol is for, starting at 1. Letters match
+    /// either case, unless the symbols use both
+    pub(crate) fn value_of(&self, symbol: &str) -> Option<usize> {
+        let position = self
+            .values
+            .iter()
+            .position(|value| value == symbol)
+            .or_else(|| {
+                self.values
+                    .iter()
+                    .position(|value| value.eq_ignore_ascii_case(symbol))
+            })?;
+
+        return Some(position + 1);
+    }
+
+    /// Returns if a board with `size` values can be read a character at a
+    /// time, because its symbols are all one character
+    pub(crate) fn single_characters(&self, size: usize) -> bool {
+        return self.values[..size]
+            .iter()
+            .chain([&self.empty])
+            .all(|symbol| symbol.chars().count() == 1);
+    }
+
+    /// Returns the widest symbol a board with `size` values prints
+    pub(crate) fn width(&self, size: usize) -> usize {
+        return self.values[..size]
+            .iter()
+            .chain([&self.empty])
+            .map(|symbol| symbol.chars().count())
+            .max()
+            .unwrap_or(1);
+    }
+
+    /// Returns if a character can be in a board file without being a
+    /// space. This is whitespace, and the lines printed between boxes
+    pub(crate) fn is_layout_character(character: char) -> bool {
+        return character.is_whitespace() || matches!(character, '|' | '-' | '+');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candidates, Geometry, SudokuBoard, SudokuError, SudokuValue, Symbols};
+
+    /// Returns the value of every space, row by row, with 0 for empty ones
+    fn values(board: &SudokuBoard) -> Vec<usize> {
+        let size = board.geometry().size();
+
+        return (0..size * size)
+            .map(|index| board.value((index / size, index % size)).unwrap_or(0))
+            .collect();
+    }
+
+    /// Prints the board with `symbols`, reads it back, and checks it's the
+    /// same board, printed the same way. Returns what was printed
+    fn round_trip(board: &SudokuBoard, symbols: Symbols) -> String {
+        let text = board.to_string_with_symbols(&symbols).unwrap();
+        let geometry = board.geometry().clone();
+        let read = SudokuBoard::from_str_with_symbols(&text, geometry, symbols).unwrap();
+
+        assert_eq!(values(&read), values(board));
+        assert_eq!(read.to_string(), text);
+
+        return text;
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let mut board = SudokuBoard::from_grid(&[0; 256], Geometry::new(4, 4).unwrap()).unwrap();
+        assert!(board.solve());
+
+        let text = round_trip(&board, Symbols::hex());
+        let first_row: String = text.lines().next().unwrap().split_whitespace().collect();
+        let mut first_row: Vec<char> = first_row.replace('|', "").chars().collect();
+        first_row.sort();
+
+        assert_eq!(first_row, "0123456789ABCDEF".chars().collect::<Vec<char>>());
+    }
+
+    #[test]
+    fn letter_round_trip() {
+        let grid = [1, 0, 0, 0, 0, 0, 3, 0, 0, 4, 0, 0, 0, 0, 0, 2];
+        let board = SudokuBoard::from_grid(&grid, Geometry::new(2, 2).unwrap()).unwrap();
+
+        let text = round_trip(&board, Symbols::letters());
+        assert_eq!(
+            text,
+            "A . | . .\n. . | C .\n---------\n. D | . .\n. . | . B"
+        );
+    }
+
+    #[test]
+    fn custom_round_trip() {
+        let symbols = Symbols::new(["one", "two", "three", "four"], "?").unwrap();
+        let grid = [1, 0, 0, 0, 0, 0, 3, 0, 0, 4, 0, 0, 0, 0, 0, 2];
+        let board = SudokuBoard::from_grid(&grid, Geometry::new(2, 2).unwrap()).unwrap();
+
+        let text = round_trip(&board, symbols);
+        assert!(text.starts_with("  one     ? |     ?     ?"));
+
+        let mut solved = board.clone();
+        assert!(solved.solve());
+        round_trip(&solved, Symbols::from_chars("wxyz", '_').unwrap());
+    }
+
+    #[test]
+    fn empty_marker() {
+        let known = SudokuValue::Known(3);
+        let empty = SudokuValue::Unknown(Candidates::all(9));
+        assert_eq!(known.to_string(), "3");
+        assert_eq!(empty.to_string(), "X");
+        assert_eq!(empty.symbol(&Symbols::hex()), ".");
+
+        // The numbers' empty symbol, '0', and other letters are all empty
+        let board: SudokuBoard = format!("X0a{}", "0".repeat(78)).parse().unwrap();
+        assert_eq!(values(&board), vec![0; 81]);
+        assert!(board.to_string().starts_with("X X X | X X X"));
+
+        // Other symbols' empty markers are read as empty spaces
+        let symbols = Symbols::from_chars("wxyz", '_').unwrap();
+        let text = "w_x_ ____ ____ ____";
+        let board = SudokuBoard::from_str_with_symbols(text, Geometry::new(2, 2).unwrap(), symbols)
+            .unwrap();
+        assert_eq!(board.value((0, 0)), Some(1));
+        assert_eq!(board.value((0, 1)), None);
+        assert_eq!(board.value((0, 2)), Some(2));
+    }
+
+    #[test]
+    fn not_enough_symbols() {
+        let board = SudokuBoard::from_grid(&[0; 625], Geometry::new(5, 5).unwrap()).unwrap();
+
+        assert!(matches!(
+            board.to_string_with_symbols(&Symbols::hex()),
+            Result::Err(SudokuError::NotEnoughSymbols { .. })
+        ));
+    }
+}
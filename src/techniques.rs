@@ -0,0 +1,559 @@
+//! Logical solving techniques, used when filling in single values gets
+//! stuck, before falling back to guessing
+//!
+//! Each technique looks for a pattern in the possible values of the board,
+//! and returns a `Deduction` listing the pattern and the values it lets us
+//! fill in or remove. Techniques don't change the board themselves
+
+use std::fmt;
+
+use crate::{Geometry, SudokuBoard, Unit};
+
+mod almost_locked_sets;
+mod chains;
+mod fish;
+mod intersections;
+mod single_digit;
+mod subsets;
+mod uniqueness;
+mod wings;
+
+#[cfg(test)]
+mod tests;
+
+/// A logical solving technique. Techniques are ordered from easiest to
+/// hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    Pointing,
+    BoxLineReduction,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    XYWing,
+    XYZWing,
+    WWing,
+    SimpleColoring,
+    UniqueRectangleType1,
+    UniqueRectangleType2,
+    UniqueRectangleType3,
+    UniqueRectangleType4,
+    NakedQuad,
+    Jellyfish,
+    HiddenQuad,
+    BugPlusOne,
+    MultiColoring,
+    XChain,
+    AlternatingInferenceChain,
+    AlsXz,
+    AlsXyWing,
+    DeathBlossom,
+    CellForcingChain,
+    UnitForcingChain,
+}
+
+impl Technique {
+    /// Every technique, from easiest to hardest
+    pub const ALL: [Technique; 28] = [
+        Technique::Pointing,
+        Technique::BoxLineReduction,
+        Technique::NakedPair,
+        Technique::XWing,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::Swordfish,
+        Technique::HiddenTriple,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::SimpleColoring,
+        Technique::UniqueRectangleType1,
+        Technique::UniqueRectangleType2,
+        Technique::UniqueRectangleType3,
+        Technique::UniqueRectangleType4,
+        Technique::NakedQuad,
+        Technique::Jellyfish,
+        Technique::HiddenQuad,
+        Technique::BugPlusOne,
+        Technique::MultiColoring,
+        Technique::XChain,
+        Technique::AlternatingInferenceChain,
+        Technique::AlsXz,
+        Technique::AlsXyWing,
+        Technique::DeathBlossom,
+        Technique::CellForcingChain,
+        Technique::UnitForcingChain,
+    ];
+
+    /// Returns the name of the technique, like "Naked Pair"
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::Pointing => "Pointing",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::XWing => "X-Wing",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::Swordfish => "Swordfish",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::UniqueRectangleType1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangleType2 => "Unique Rectangle Type 2",
+            Technique::UniqueRectangleType3 => "Unique Rectangle Type 3",
+            Technique::UniqueRectangleType4 => "Unique Rectangle Type 4",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::MultiColoring => "Multi-Coloring",
+            Technique::XChain => "X-Chain",
+            Technique::AlternatingInferenceChain => "Alternating Inference Chain",
+            Technique::AlsXz => "ALS-XZ",
+            Technique::AlsXyWing => "ALS-XY-Wing",
+            Technique::DeathBlossom => "Death Blossom",
+            Technique::CellForcingChain => "Cell Forcing Chain",
+            Technique::UnitForcingChain => "Unit Forcing Chain",
+        }
+    }
+
+    /// Returns if the technique only works on a sudoku with one solution.
+    /// These are only used if the board is set to assume it has one, with
+    /// `SudokuBoard::set_assume_unique_solution`
+    pub fn needs_unique_solution(self) -> bool {
+        return matches!(
+            self,
+            Technique::UniqueRectangleType1
+                | Technique::UniqueRectangleType2
+                | Technique::UniqueRectangleType3
+                | Technique::UniqueRectangleType4
+                | Technique::BugPlusOne
+        );
+    }
+
+    /// Returns if the technique can be used on boards with this geometry.
+    /// Almost locked sets and the chains keep sets of spaces as 128 bit
+    /// masks, and look at every subset of each unit's spaces, so they only
+    /// work on boards with up to 128 spaces
+    pub fn works_on(self, geometry: &Geometry) -> bool {
+        let uses_space_sets = matches!(
+            self,
+            Technique::AlternatingInferenceChain
+                | Technique::AlsXz
+                | Technique::AlsXyWing
+                | Technique::DeathBlossom
+                | Technique::CellForcingChain
+                | Technique::UnitForcingChain
+        );
+
+        return !uses_space_sets || geometry.spaces() <= Spaces::BITS as usize;
+    }
+
+    /// Returns how hard the technique is, on a scale like Sudoku Explainer's
+    /// ratings. Harder techniques never score less than easier ones
+    pub fn score(self) -> f32 {
+        match self {
+            Technique::Pointing => 2.6,
+            Technique::BoxLineReduction => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Swordfish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::SimpleColoring => 4.5,
+            Technique::UniqueRectangleType1 => 4.5,
+            Technique::UniqueRectangleType2 => 4.6,
+            Technique::UniqueRectangleType3 => 4.6,
+            Technique::UniqueRectangleType4 => 4.6,
+            Technique::NakedQuad => 5.0,
+            Technique::Jellyfish => 5.2,
+            Technique::HiddenQuad => 5.4,
+            Technique::BugPlusOne => 5.6,
+            Technique::MultiColoring => 5.8,
+            Technique::XChain => 6.6,
+            Technique::AlternatingInferenceChain => 7.0,
+            Technique::AlsXz => 7.5,
+            Technique::AlsXyWing => 7.8,
+            Technique::DeathBlossom => 8.0,
+            Technique::CellForcingChain => 8.3,
+            Technique::UnitForcingChain => 8.5,
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A set of techniques, used to choose which ones the solver can use
+///
+/// ```
+/// use rsudoku::{Technique, TechniqueSet};
+///
+/// let mut techniques = TechniqueSet::all();
+/// techniques.remove(Technique::HiddenQuad);
+///
+/// assert!(techniques.contains(Technique::NakedPair));
+/// assert!(!techniques.contains(Technique::HiddenQuad));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TechniqueSet(u64);
+
+impl TechniqueSet {
+    /// Returns a set with no techniques. The solver only fills in single
+    /// values, then guesses
+    pub fn empty() -> TechniqueSet {
+        return TechniqueSet(0);
+    }
+
+    /// Returns a set of every technique
+    pub fn all() -> TechniqueSet {
+        return Technique::ALL.into_iter().collect();
+    }
+
+    /// Returns if `technique` is in the set
+    pub fn contains(self, technique: Technique) -> bool {
+        return self.0 & (1 << technique as u64) != 0;
+    }
+
+    /// Adds `technique` to the set
+    pub fn insert(&mut self, technique: Technique) {
+        self.0 |= 1 << technique as u64;
+    }
+
+    /// Removes `technique` from the set
+    pub fn remove(&mut self, technique: Technique) {
+        self.0 &= !(1 << technique as u64);
+    }
+
+    /// Returns an iterator over the techniques in the set, easiest first
+    pub fn iter(self) -> impl Iterator<Item = Technique> {
+        return Technique::ALL
+            .into_iter()
+            .filter(move |technique| self.contains(*technique));
+    }
+}
+
+impl Default for TechniqueSet {
+    /// Locked candidates and naked and hidden subsets. The other techniques
+    /// find less, and are slow to check for at every guess, so they have
+    /// to be turned on. See `SudokuBoard::set_techniques`
+    fn default() -> Self {
+        return [
+            Technique::Pointing,
+            Technique::BoxLineReduction,
+            Technique::NakedPair,
+            Technique::HiddenPair,
+            Technique::NakedTriple,
+            Technique::HiddenTriple,
+            Technique::NakedQuad,
+            Technique::HiddenQuad,
+        ]
+        .into_iter()
+        .collect();
+    }
+}
+
+impl FromIterator<Technique> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = Technique>>(techniques: I) -> Self {
+        let mut set = TechniqueSet::empty();
+
+        for technique in techniques {
+            set.insert(technique);
+        }
+
+        return set;
+    }
+}
+
+/// The pattern a technique found on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// Spaces in a unit that between them can only be `values` (naked), or
+    /// are the only spaces in the unit that can be `values` (hidden)
+    Subset {
+        unit: Unit,
+        spaces: Vec<(usize, usize)>,
+        values: Vec<usize>,
+    },
+
+    /// A value that, in `box_unit`, can only go in `spaces` on `line`
+    /// (pointing), or on `line`, can only go in `spaces` in `box_unit`
+    /// (box/line reduction)
+    LockedCandidates {
+        value: usize,
+        box_unit: Unit,
+        line: Unit,
+        spaces: Vec<(usize, usize)>,
+    },
+
+    /// Rows or columns (`base_sets`) where `value` can only go in the
+    /// crossing `cover_sets`
+    Fish {
+        value: usize,
+        base_sets: Vec<Unit>,
+        cover_sets: Vec<Unit>,
+    },
+
+    /// Two pincer spaces, one of which must be `value`, because of the
+    /// pivots. XY-Wings and XYZ-Wings have one pivot space adjacent to both
+    /// pincers. A W-Wing's pivots are the only two spaces in a unit with
+    /// the pincers' other value
+    Wing {
+        value: usize,
+        pivots: Vec<(usize, usize)>,
+        pincers: Vec<(usize, usize)>,
+    },
+
+    /// Clusters of spaces joined by strong links for `value`, each colored
+    /// in two colors. `colors` has both colors of each cluster in turn, and
+    /// in each cluster, one color is all `value` and the other has none
+    Coloring {
+        value: usize,
+        colors: Vec<Vec<(usize, usize)>>,
+    },
+
+    /// Four spaces in the corners of a rectangle, across two boxes, that
+    /// could all be `values`
+    UniqueRectangle {
+        spaces: Vec<(usize, usize)>,
+        values: Vec<usize>,
+    },
+
+    /// The only unknown space with more than two possible values, when the
+    /// rest of the board is a bivalue universal grave
+    Bug { space: (usize, usize) },
+
+    /// A chain with links alternating between strong and weak, starting and
+    /// ending with a strong link. One of the ends must be true
+    Chain(Chain),
+
+    /// Almost locked sets, which are N spaces in a unit with N + 1 possible
+    /// values between them. For an ALS-XZ, two sets with a restricted
+    /// common value. For an ALS-XY-Wing, two sets and then the pivot set,
+    /// with a restricted common value between each set and the pivot.
+    /// `value` has to be in one of the first two sets
+    AlmostLockedSets {
+        sets: Vec<Vec<(usize, usize)>>,
+        restricted_commons: Vec<usize>,
+        value: usize,
+    },
+
+    /// A stem space, and an almost locked set for each of its possible
+    /// values, in order. `value` has to be in one of the petals
+    DeathBlossom {
+        stem: (usize, usize),
+        petals: Vec<Vec<(usize, usize)>>,
+        value: usize,
+    },
+
+    /// Chains from each value of a space, or from each space for a value in
+    /// a unit. Each chain starts by assuming its first node is true, and
+    /// they all end with the same conclusion about their last node
+    ForcingChains { chains: Vec<Chain> },
+}
+
+/// A chain of nodes, where `links[n]` links `nodes[n]` and `nodes[n + 1]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chain {
+    pub nodes: Vec<ChainNode>,
+    pub links: Vec<Link>,
+}
+
+/// A node in a chain, which is true if `value` is in one of `spaces`.
+/// Grouped nodes have more than one space, all where a box crosses a row or
+/// column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainNode {
+    pub spaces: Vec<(usize, usize)>,
+    pub value: usize,
+}
+
+/// A link between two nodes in a chain. Links between nodes with the same
+/// value are in a unit, and links between different values are in a space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Link {
+    /// At least one of the nodes is true
+    Strong,
+
+    /// At most one of the nodes is true
+    Weak,
+}
+
+/// A step found by a technique. It isn't applied to the board until
+/// `SudokuBoard::apply_deduction` is called
+///
+/// Points are (row, column), starting at 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    pub pattern: Pattern,
+
+    /// Spaces that can be filled in, and their value
+    pub placements: Vec<((usize, usize), usize)>,
+
+    /// Possible values that can be removed from spaces
+    pub eliminations: Vec<((usize, usize), usize)>,
+}
+
+/// Returns the first deduction `technique` finds on the board, if any
+///
+/// The board must have had its initial check, so the possible values of
+/// each space are up to date
+///
+/// Techniques that don't work on the board find nothing. See
+/// `Technique::works_on`
+pub(crate) fn find(board: &SudokuBoard, technique: Technique) -> Option<Deduction> {
+    if technique.needs_unique_solution() && !board.assumes_unique_solution() {
+        return None;
+    }
+
+    // The patterns these look for could be broken by a constraint, so they
+    // don't mean the sudoku would have two solutions
+    if technique.needs_unique_solution() && !board.constraints.is_empty() {
+        return None;
+    }
+
+    if !technique.works_on(board.geometry()) {
+        return None;
+    }
+
+    match technique {
+        Technique::Pointing => intersections::find_pointing(board),
+        Technique::BoxLineReduction => intersections::find_box_line_reduction(board),
+        Technique::NakedPair => subsets::find_naked_subset(board, 2),
+        Technique::NakedTriple => subsets::find_naked_subset(board, 3),
+        Technique::NakedQuad => subsets::find_naked_subset(board, 4),
+        Technique::HiddenPair => subsets::find_hidden_subset(board, 2),
+        Technique::HiddenTriple => subsets::find_hidden_subset(board, 3),
+        Technique::HiddenQuad => subsets::find_hidden_subset(board, 4),
+        Technique::XWing => fish::find_fish(board, 2),
+        Technique::Swordfish => fish::find_fish(board, 3),
+        Technique::Jellyfish => fish::find_fish(board, 4),
+        Technique::XYWing => wings::find_xy_wing(board),
+        Technique::XYZWing => wings::find_xyz_wing(board),
+        Technique::WWing => wings::find_w_wing(board),
+        Technique::UniqueRectangleType1 => uniqueness::find_unique_rectangle(board, 1),
+        Technique::UniqueRectangleType2 => uniqueness::find_unique_rectangle(board, 2),
+        Technique::UniqueRectangleType3 => uniqueness::find_unique_rectangle(board, 3),
+        Technique::UniqueRectangleType4 => uniqueness::find_unique_rectangle(board, 4),
+        Technique::BugPlusOne => uniqueness::find_bug_plus_one(board),
+        Technique::SimpleColoring => single_digit::find_simple_coloring(board),
+        Technique::MultiColoring => single_digit::find_multi_coloring(board),
+        Technique::XChain => single_digit::find_x_chain(board),
+        Technique::AlternatingInferenceChain => chains::find_alternating_inference_chain(board),
+        Technique::AlsXz => almost_locked_sets::find_als_xz(board),
+        Technique::AlsXyWing => almost_locked_sets::find_als_xy_wing(board),
+        Technique::DeathBlossom => almost_locked_sets::find_death_blossom(board),
+        Technique::CellForcingChain => chains::find_cell_forcing_chain(board),
+        Technique::UnitForcingChain => chains::find_unit_forcing_chain(board),
+    }
+}
+
+/// Returns if two spaces are adjacent, so are in the same row, column, or
+/// box. A space isn't adjacent to itself
+fn are_peers(board: &SudokuBoard, first: usize, second: usize) -> bool {
+    return board.geometry().peers(first).binary_search(&second).is_ok();
+}
+
+/// A set of spaces, where bit `n` is space `n`
+type Spaces = u128;
+
+/// One more than the most values a board with 128 spaces or less can have,
+/// so arrays indexed by value are big enough for any board `Spaces` works
+/// on
+const VALUES: usize = 12;
+
+/// Returns the spaces adjacent to each space, as sets
+fn peer_spaces(board: &SudokuBoard) -> Vec<Spaces> {
+    let geometry = board.geometry();
+
+    return (0..geometry.spaces())
+        .map(|index| {
+            geometry
+                .peers(index)
+                .iter()
+                .fold(0, |spaces, &peer| spaces | 1 << peer)
+        })
+        .collect();
+}
+
+/// Returns the spaces that could be each value
+fn candidate_spaces(board: &SudokuBoard) -> [Spaces; VALUES] {
+    let mut candidates = [0; VALUES];
+
+    for index in 0..board.geometry().spaces() {
+        for value in board.possible_values(index).iter() {
+            candidates[value] |= 1 << index;
+        }
+    }
+
+    return candidates;
+}
+
+/// Returns the spaces adjacent to every space in `spaces`
+fn adjacent_to_all(spaces: Spaces, peers: &[Spaces]) -> Spaces {
+    let mut adjacent = !0;
+
+    for (index, index_peers) in peers.iter().enumerate() {
+        if spaces & 1 << index != 0 {
+            adjacent &= index_peers;
+        }
+    }
+
+    return adjacent;
+}
+
+/// Turns a set of spaces into points
+fn points_in(board: &SudokuBoard, spaces: Spaces) -> Vec<(usize, usize)> {
+    return points_of(
+        board,
+        (0..board.geometry().spaces()).filter(|index| spaces & 1 << index != 0),
+    );
+}
+
+/// Turns a list of space indexes into points
+fn points_of(board: &SudokuBoard, indexes: impl IntoIterator<Item = usize>) -> Vec<(usize, usize)> {
+    return indexes
+        .into_iter()
+        .map(|index| board.geometry().point_of(index))
+        .collect();
+}
+
+/// Moves `combination` on to the next combination of its length from
+/// `0..item_count`, in lexicographic order
+///
+/// Start with `[0, 1, .., length - 1]`. Returns false once every
+/// combination has been seen
+fn next_combination(combination: &mut [usize], item_count: usize) -> bool {
+    let length = combination.len();
+
+    // Find the rightmost index that can still be moved up
+    let mut position = length;
+    while position > 0 {
+        position -= 1;
+
+        if combination[position] < item_count - length + position {
+            combination[position] += 1;
+
+            for next in position + 1..length {
+                combination[next] = combination[next - 1] + 1;
+            }
+
+            return true;
+        }
+    }
+
+    return false;
+}
@@ -0,0 +1,310 @@
+//! Almost locked sets: ALS-XZ, ALS-XY-Wing, and Death Blossom
+//!
+//! An almost locked set is N spaces in a unit that between them have N + 1
+//! possible values. If any one of those values is removed, the rest have
+//! to go in those spaces. A single space with two possible values is the
+//! smallest one
+//!
+//! Two sets have a restricted common value if every space that could be
+//! that value in one set is adjacent to every space that could be it in the
+//! other. Then at most one of the sets can have it
+
+use std::collections::HashSet;
+
+use crate::{Candidates, SudokuBoard};
+
+use super::{
+    adjacent_to_all, candidate_spaces, peer_spaces, points_in, Deduction, Pattern, Spaces,
+    Technique, VALUES,
+};
+
+/// An almost locked set on the board
+struct AlmostLockedSet {
+    spaces: Spaces,
+    values: Candidates,
+
+    /// The spaces in the set that could be each value
+    value_spaces: [Spaces; VALUES],
+
+    /// The spaces adjacent to every space in the set that could be each
+    /// value
+    adjacent: [Spaces; VALUES],
+}
+
+/// Finds two sets with a restricted common value x. One of the sets doesn't
+/// have x, so it's locked, and has all its other values. Any other value z
+/// they share has to be in one of them, so it can be removed from spaces
+/// adjacent to every z in both
+pub(crate) fn find_als_xz(board: &SudokuBoard) -> Option<Deduction> {
+    let candidates = candidate_spaces(board);
+    let sets = almost_locked_sets(board);
+
+    for (first, first_set) in sets.iter().enumerate() {
+        for second_set in &sets[first + 1..] {
+            for restricted in restricted_commons(first_set, second_set).iter() {
+                for value in (first_set.values & second_set.values).iter() {
+                    if value == restricted {
+                        continue;
+                    }
+
+                    let eliminations =
+                        eliminations(board, &[first_set, second_set], value, &candidates);
+
+                    if !eliminations.is_empty() {
+                        return Some(Deduction {
+                            technique: Technique::AlsXz,
+                            pattern: Pattern::AlmostLockedSets {
+                                sets: vec![
+                                    points_in(board, first_set.spaces),
+                                    points_in(board, second_set.spaces),
+                                ],
+                                restricted_commons: vec![restricted],
+                                value,
+                            },
+                            placements: Vec::new(),
+                            eliminations,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a pivot set, with a restricted common value x with one set, and y
+/// with another. The pivot can't be missing both x and y, so one of the
+/// other sets is locked. Any value z those two share has to be in one of
+/// them
+pub(crate) fn find_als_xy_wing(board: &SudokuBoard) -> Option<Deduction> {
+    let candidates = candidate_spaces(board);
+    let sets = almost_locked_sets(board);
+
+    for pivot in &sets {
+        let linked: Vec<(&AlmostLockedSet, Candidates)> = sets
+            .iter()
+            .map(|set| (set, restricted_commons(pivot, set)))
+            .filter(|(_, restricted)| restricted.len() > 0)
+            .collect();
+
+        for (first, &(first_set, first_restricted)) in linked.iter().enumerate() {
+            for &(second_set, second_restricted) in &linked[first + 1..] {
+                if first_set.spaces & second_set.spaces != 0 {
+                    continue;
+                }
+
+                for x in first_restricted.iter() {
+                    for y in second_restricted.iter().filter(|&y| y != x) {
+                        for value in (first_set.values & second_set.values).iter() {
+                            if value == x || value == y {
+                                continue;
+                            }
+
+                            let eliminations =
+                                eliminations(board, &[first_set, second_set], value, &candidates);
+
+                            if !eliminations.is_empty() {
+                                return Some(Deduction {
+                                    technique: Technique::AlsXyWing,
+                                    pattern: Pattern::AlmostLockedSets {
+                                        sets: vec![
+                                            points_in(board, first_set.spaces),
+                                            points_in(board, second_set.spaces),
+                                            points_in(board, pivot.spaces),
+                                        ],
+                                        restricted_commons: vec![x, y],
+                                        value,
+                                    },
+                                    placements: Vec::new(),
+                                    eliminations,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a stem space, and a petal set for each of its possible values,
+/// where every space in the petal that could be that value is adjacent to
+/// the stem. Whichever value the stem is, that petal is locked. So any
+/// value z every petal has, but the stem doesn't, is in one of the petals
+pub(crate) fn find_death_blossom(board: &SudokuBoard) -> Option<Deduction> {
+    let peers = peer_spaces(board);
+    let candidates = candidate_spaces(board);
+    let sets = almost_locked_sets(board);
+
+    for (stem, stem_peers) in peers.iter().enumerate() {
+        let stem_values = board.possible_values(stem);
+        if stem_values.len() < 2 {
+            continue;
+        }
+
+        // The sets that could be the petal for each of the stem's values
+        let petal_options: Vec<Vec<&AlmostLockedSet>> = stem_values
+            .iter()
+            .map(|value| {
+                sets.iter()
+                    .filter(|set| set.spaces & 1 << stem == 0)
+                    .filter(|set| set.values.contains(value))
+                    .filter(|set| set.value_spaces[value] & !stem_peers == 0)
+                    .collect()
+            })
+            .collect();
+
+        let mut petals = Vec::new();
+        let shared = Candidates::all(board.geometry().size()) - stem_values;
+
+        if let Some(value) = find_petals(board, &petal_options, &mut petals, shared, &candidates) {
+            let eliminations = eliminations(board, &petals, value, &candidates);
+
+            return Some(Deduction {
+                technique: Technique::DeathBlossom,
+                pattern: Pattern::DeathBlossom {
+                    stem: board.geometry().point_of(stem),
+                    petals: petals
+                        .iter()
+                        .map(|petal| points_in(board, petal.spaces))
+                        .collect(),
+                    value,
+                },
+                placements: Vec::new(),
+                eliminations,
+            });
+        }
+    }
+
+    return None;
+}
+
+/// Picks a petal for each of the stem's values in turn, which doesn't
+/// overlap the petals picked so far, and still shares a value with them.
+/// Returns a shared value with eliminations once every value has a petal,
+/// leaving the petals in `petals`
+fn find_petals<'a>(
+    board: &SudokuBoard,
+    petal_options: &[Vec<&'a AlmostLockedSet>],
+    petals: &mut Vec<&'a AlmostLockedSet>,
+    shared: Candidates,
+    candidates: &[Spaces; VALUES],
+) -> Option<usize> {
+    if petals.len() == petal_options.len() {
+        return shared
+            .iter()
+            .find(|&value| !eliminations(board, petals, value, candidates).is_empty());
+    }
+
+    let used = petals.iter().fold(0, |used, petal| used | petal.spaces);
+
+    for &petal in &petal_options[petals.len()] {
+        let petal_shared = shared & petal.values;
+        if petal.spaces & used != 0 || petal_shared.len() == 0 {
+            continue;
+        }
+
+        petals.push(petal);
+
+        let found = find_petals(board, petal_options, petals, petal_shared, candidates);
+        if found.is_some() {
+            return found;
+        }
+
+        petals.pop();
+    }
+
+    return None;
+}
+
+/// Returns every almost locked set on the board. A set in more than one
+/// unit is only listed once
+fn almost_locked_sets(board: &SudokuBoard) -> Vec<AlmostLockedSet> {
+    let peers = peer_spaces(board);
+    let mut sets = Vec::new();
+    let mut seen = HashSet::new();
+
+    for unit in board.geometry().units() {
+        let unknown: Vec<usize> = unit
+            .iter()
+            .copied()
+            .filter(|&index| board.possible_values(index).len() > 0)
+            .collect();
+
+        // Every subset of the unknown spaces, as a bitmask of positions
+        for subset in 1_usize..1 << unknown.len() {
+            let mut spaces: Spaces = 0;
+            let mut values = Candidates::default();
+
+            for (position, &index) in unknown.iter().enumerate() {
+                if subset & 1 << position != 0 {
+                    spaces |= 1 << index;
+                    values = values | board.possible_values(index);
+                }
+            }
+
+            if values.len() != subset.count_ones() as usize + 1 || !seen.insert(spaces) {
+                continue;
+            }
+
+            let mut value_spaces = [0; VALUES];
+            let mut adjacent = [0; VALUES];
+
+            for value in values.iter() {
+                value_spaces[value] = unknown
+                    .iter()
+                    .filter(|&&index| spaces & 1 << index != 0)
+                    .filter(|&&index| board.possible_values(index).contains(value))
+                    .fold(0, |value_spaces, &index| value_spaces | 1 << index);
+                adjacent[value] = adjacent_to_all(value_spaces[value], &peers);
+            }
+
+            sets.push(AlmostLockedSet {
+                spaces,
+                values,
+                value_spaces,
+                adjacent,
+            });
+        }
+    }
+
+    return sets;
+}
+
+/// Returns the restricted common values of two sets that don't overlap
+fn restricted_commons(first: &AlmostLockedSet, second: &AlmostLockedSet) -> Candidates {
+    let mut restricted = Candidates::default();
+
+    if first.spaces & second.spaces != 0 {
+        return restricted;
+    }
+
+    for value in (first.values & second.values).iter() {
+        if second.value_spaces[value] & !first.adjacent[value] == 0 {
+            restricted.insert(value);
+        }
+    }
+
+    return restricted;
+}
+
+/// Removes `value` from every space outside the sets that's adjacent to
+/// every space in them that could be `value`
+fn eliminations(
+    board: &SudokuBoard,
+    sets: &[&AlmostLockedSet],
+    value: usize,
+    candidates: &[Spaces; VALUES],
+) -> Vec<((usize, usize), usize)> {
+    let spaces = sets.iter().fold(candidates[value], |spaces, set| {
+        spaces & set.adjacent[value] & !set.spaces
+    });
+
+    return points_in(board, spaces)
+        .into_iter()
+        .map(|point| (point, value))
+        .collect();
+}
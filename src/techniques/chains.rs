@@ -0,0 +1,412 @@
+//! Alternating inference chains, and cell and unit forcing chains
+//!
+//! Chains are made of nodes, which are a value in a space, or in a group of
+//! spaces where a box crosses a row or column. Nodes are strongly linked if
+//! one of them has to be true, and weakly linked if they can't both be
+//! true. Links are either between the same value in a unit, or between
+//! values in the same space
+
+use std::collections::VecDeque;
+
+use crate::SudokuBoard;
+
+use super::{
+    adjacent_to_all, candidate_spaces, peer_spaces, points_in, Chain, ChainNode, Deduction, Link,
+    Pattern, Spaces, Technique, VALUES,
+};
+
+/// A node in the graph. `value` is in one of `spaces`
+struct Node {
+    spaces: Spaces,
+    value: usize,
+
+    /// The spaces where each value couldn't be true if this node was, so
+    /// could be removed if this node is true
+    conflicts: [Spaces; VALUES],
+}
+
+/// Every node and link on the board
+struct Graph<'a> {
+    board: &'a SudokuBoard,
+    nodes: Vec<Node>,
+    strong: Vec<Vec<usize>>,
+
+    /// Weak links. Every strong link is also a weak link
+    weak: Vec<Vec<usize>>,
+}
+
+/// Finds a chain of nodes, with links alternating between strong and weak,
+/// that starts and ends with a strong link. If the first node is false,
+/// then following the links, the last has to be true. So anything that
+/// can't be true with both the first and last node can be removed
+pub(crate) fn find_alternating_inference_chain(board: &SudokuBoard) -> Option<Deduction> {
+    let graph = Graph::new(board);
+    let mut previous = vec![usize::MAX; graph.nodes.len() * 2];
+
+    for start in 0..graph.nodes.len() {
+        if graph.strong[start].is_empty() {
+            continue;
+        }
+
+        // Breadth first search, so the shortest chains are found first.
+        // Each node is visited as off (false) or on (true), stored as
+        // `node * 2 + on`
+        previous.fill(usize::MAX);
+        previous[start * 2] = start * 2;
+        let mut queue = VecDeque::from([start * 2]);
+
+        while let Some(state) = queue.pop_front() {
+            let (node, on) = (state / 2, state % 2 == 1);
+            let next_nodes = if on {
+                &graph.weak[node]
+            } else {
+                &graph.strong[node]
+            };
+
+            for &next_node in next_nodes {
+                let next_state = next_node * 2 + !on as usize;
+                if previous[next_state] != usize::MAX {
+                    continue;
+                }
+
+                previous[next_state] = state;
+                queue.push_back(next_state);
+
+                if on || next_node == start {
+                    continue;
+                }
+
+                let eliminations = graph.common_conflicts(start, next_node);
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                let chain = graph.chain(&previous, start * 2, next_state);
+
+                // A single strong link is left to the easier techniques
+                if chain.links.len() < 3 {
+                    continue;
+                }
+
+                return Some(Deduction {
+                    technique: Technique::AlternatingInferenceChain,
+                    pattern: Pattern::Chain(chain),
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a space where, whichever value it is, following the links from
+/// that value leads to the same conclusion, so the conclusion must be true
+pub(crate) fn find_cell_forcing_chain(board: &SudokuBoard) -> Option<Deduction> {
+    let graph = Graph::new(board);
+
+    for index in 0..board.geometry().spaces() {
+        let starts: Vec<usize> = (0..graph.nodes.len())
+            .filter(|&node| graph.nodes[node].spaces == 1 << index)
+            .collect();
+
+        if starts.len() < 2 {
+            continue;
+        }
+
+        if let Some(deduction) = find_forcing_chain(&graph, &starts, Technique::CellForcingChain) {
+            return Some(deduction);
+        }
+    }
+
+    return None;
+}
+
+/// Finds a value in a row, column, or box where, whichever space it goes
+/// in, following the links from that space leads to the same conclusion
+pub(crate) fn find_unit_forcing_chain(board: &SudokuBoard) -> Option<Deduction> {
+    let graph = Graph::new(board);
+
+    for unit in board.geometry().units() {
+        for value in 1..=board.geometry().size() {
+            let starts: Vec<usize> = (0..graph.nodes.len())
+                .filter(|&node| graph.nodes[node].value == value)
+                .filter(|&node| {
+                    unit.iter()
+                        .any(|&index| graph.nodes[node].spaces == 1 << index)
+                })
+                .collect();
+
+            if starts.len() < 2 {
+                continue;
+            }
+
+            if let Some(deduction) =
+                find_forcing_chain(&graph, &starts, Technique::UnitForcingChain)
+            {
+                return Some(deduction);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Assumes each of `starts` is true in turn, and looks for a value in a
+/// space that follows from all of them. One of the starts must be true, so
+/// the value must be there, or not there
+fn find_forcing_chain(graph: &Graph, starts: &[usize], technique: Technique) -> Option<Deduction> {
+    let reached: Vec<Vec<usize>> = starts
+        .iter()
+        .map(|&start| graph.implications(start * 2 + 1))
+        .collect();
+
+    // Only conclusions about a single space are useful. Being true is
+    // checked first, since it fills in a space
+    for on in [1, 0] {
+        for (node, node_data) in graph.nodes.iter().enumerate() {
+            if node_data.spaces.count_ones() != 1 {
+                continue;
+            }
+
+            let state = node * 2 + on;
+            if reached.iter().any(|previous| previous[state] == usize::MAX) {
+                continue;
+            }
+
+            let chains = starts
+                .iter()
+                .zip(&reached)
+                .map(|(&start, previous)| graph.chain(previous, start * 2 + 1, state))
+                .collect();
+
+            let point = graph
+                .board
+                .geometry()
+                .point_of(node_data.spaces.trailing_zeros() as usize);
+            let (placements, eliminations) = if on == 1 {
+                (vec![(point, node_data.value)], Vec::new())
+            } else {
+                (Vec::new(), vec![(point, node_data.value)])
+            };
+
+            return Some(Deduction {
+                technique,
+                pattern: Pattern::ForcingChains { chains },
+                placements,
+                eliminations,
+            });
+        }
+    }
+
+    return None;
+}
+
+impl<'a> Graph<'a> {
+    /// Finds every node and link from the possible values on the board
+    fn new(board: &'a SudokuBoard) -> Graph<'a> {
+        let geometry = board.geometry();
+        let size = geometry.size();
+        let peers = peer_spaces(board);
+        let candidates = candidate_spaces(board);
+
+        let mut nodes = Vec::new();
+
+        for index in 0..geometry.spaces() {
+            for value in board.possible_values(index).iter() {
+                nodes.push(Node::new(1 << index, value, &peers, &candidates));
+            }
+        }
+
+        // Groups, where a box crosses a row or column
+        for box_spaces in &geometry.units()[size * 2..] {
+            for line in 0..size * 2 {
+                let crossing = box_spaces
+                    .iter()
+                    .filter(|&&index| geometry.units_of_cell(index).contains(&line))
+                    .fold(0, |spaces: Spaces, &index| spaces | 1 << index);
+
+                for (value, value_candidates) in candidates.iter().enumerate().skip(1) {
+                    let group = crossing & value_candidates;
+                    if group.count_ones() >= 2 {
+                        nodes.push(Node::new(group, value, &peers, &candidates));
+                    }
+                }
+            }
+        }
+
+        let mut graph = Graph {
+            board,
+            strong: vec![Vec::new(); nodes.len()],
+            weak: vec![Vec::new(); nodes.len()],
+            nodes,
+        };
+
+        // Links between the same value in a unit
+        for unit in geometry.units() {
+            let unit_spaces = unit
+                .iter()
+                .fold(0, |spaces: Spaces, &index| spaces | 1 << index);
+
+            for (value, value_candidates) in candidates.iter().enumerate().skip(1) {
+                let value_spaces = unit_spaces & value_candidates;
+                let unit_nodes: Vec<usize> = (0..graph.nodes.len())
+                    .filter(|&node| graph.nodes[node].value == value)
+                    .filter(|&node| graph.nodes[node].spaces & !value_spaces == 0)
+                    .collect();
+
+                for (position, &first) in unit_nodes.iter().enumerate() {
+                    for &second in &unit_nodes[position + 1..] {
+                        let first_spaces = graph.nodes[first].spaces;
+                        let second_spaces = graph.nodes[second].spaces;
+
+                        if first_spaces & second_spaces != 0 {
+                            continue;
+                        }
+
+                        add_link(&mut graph.weak, first, second);
+                        if first_spaces | second_spaces == value_spaces {
+                            add_link(&mut graph.strong, first, second);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Links between values in a space. Single space nodes come first,
+        // in order of space
+        let mut first = 0;
+        while first < graph.nodes.len() && graph.nodes[first].spaces.count_ones() == 1 {
+            let spaces = graph.nodes[first].spaces;
+            let mut last = first;
+            while last + 1 < graph.nodes.len() && graph.nodes[last + 1].spaces == spaces {
+                last += 1;
+            }
+
+            for node in first..=last {
+                for other in node + 1..=last {
+                    add_link(&mut graph.weak, node, other);
+                    if last - first == 1 {
+                        add_link(&mut graph.strong, node, other);
+                    }
+                }
+            }
+
+            first = last + 1;
+        }
+
+        return graph;
+    }
+
+    /// Returns the previous state of every state reached from `start`, by
+    /// following weak links from on nodes and strong links from off nodes.
+    /// States that aren't reached are `usize::MAX`
+    fn implications(&self, start: usize) -> Vec<usize> {
+        let mut previous = vec![usize::MAX; self.nodes.len() * 2];
+        previous[start] = start;
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(state) = queue.pop_front() {
+            let (node, on) = (state / 2, state % 2 == 1);
+            let next_nodes = if on {
+                &self.weak[node]
+            } else {
+                &self.strong[node]
+            };
+
+            for &next_node in next_nodes {
+                let next_state = next_node * 2 + !on as usize;
+                if previous[next_state] == usize::MAX {
+                    previous[next_state] = state;
+                    queue.push_back(next_state);
+                }
+            }
+        }
+
+        return previous;
+    }
+
+    /// Returns the values that can't be true if either `first` or `second`
+    /// is
+    fn common_conflicts(&self, first: usize, second: usize) -> Vec<((usize, usize), usize)> {
+        let mut eliminations = Vec::new();
+
+        for value in 1..=self.board.geometry().size() {
+            let spaces = self.nodes[first].conflicts[value] & self.nodes[second].conflicts[value];
+
+            for point in points_in(self.board, spaces) {
+                eliminations.push((point, value));
+            }
+        }
+
+        return eliminations;
+    }
+
+    /// Follows `previous` back from `end` to `start`, and returns the chain
+    /// between them
+    fn chain(&self, previous: &[usize], start: usize, end: usize) -> Chain {
+        let mut states = vec![end];
+        while *states.last().unwrap() != start {
+            states.push(previous[*states.last().unwrap()]);
+        }
+        states.reverse();
+
+        let nodes = states
+            .iter()
+            .map(|&state| {
+                let node = &self.nodes[state / 2];
+
+                ChainNode {
+                    spaces: points_in(self.board, node.spaces),
+                    value: node.value,
+                }
+            })
+            .collect();
+
+        // Going from off to on uses a strong link
+        let links = states[1..]
+            .iter()
+            .map(|&state| {
+                if state % 2 == 1 {
+                    Link::Strong
+                } else {
+                    Link::Weak
+                }
+            })
+            .collect();
+
+        return Chain { nodes, links };
+    }
+}
+
+/// Links two nodes, unless they're already linked
+fn add_link(links: &mut [Vec<usize>], first: usize, second: usize) {
+    if !links[first].contains(&second) {
+        links[first].push(second);
+        links[second].push(first);
+    }
+}
+
+impl Node {
+    fn new(spaces: Spaces, value: usize, peers: &[Spaces], candidates: &[Spaces; VALUES]) -> Node {
+        let mut conflicts = [0; VALUES];
+
+        conflicts[value] = adjacent_to_all(spaces, peers) & !spaces & candidates[value];
+
+        // The other values in the space
+        if spaces.count_ones() == 1 {
+            for (other_value, other_spaces) in candidates.iter().enumerate() {
+                if other_value != value {
+                    conflicts[other_value] |= spaces & other_spaces;
+                }
+            }
+        }
+
+        return Node {
+            spaces,
+            value,
+            conflicts,
+        };
+    }
+}
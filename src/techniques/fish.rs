@@ -0,0 +1,127 @@
+//! Basic fish: X-Wing, Swordfish, and Jellyfish
+
+use crate::{Geometry, SudokuBoard};
+
+use super::{next_combination, Deduction, Pattern, Technique};
+
+/// Finds `size` rows where a value can only go in the same `size` columns,
+/// or `size` columns where it can only go in the same `size` rows. The
+/// value has to go in one of the crossing spaces in each of those rows
+/// (the base sets), which fills up the columns (the cover sets), so it can
+/// be removed from the rest of the columns
+pub(crate) fn find_fish(board: &SudokuBoard, size: usize) -> Option<Deduction> {
+    let board_size = board.geometry().size();
+
+    for value in 1..=board_size {
+        // Rows as base sets, then columns
+        for base_start in [0, board_size] {
+            if let Some(deduction) = find_fish_for_value(board, size, value, base_start) {
+                return Some(deduction);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Looks for a fish of `value`, with the base sets being the rows or
+/// columns from `base_start` in `Geometry::units`, and the cover sets being
+/// the other kind of line
+fn find_fish_for_value(
+    board: &SudokuBoard,
+    size: usize,
+    value: usize,
+    base_start: usize,
+) -> Option<Deduction> {
+    let technique = match size {
+        2 => Technique::XWing,
+        3 => Technique::Swordfish,
+        _ => Technique::Jellyfish,
+    };
+    let geometry = board.geometry();
+    let units = geometry.units();
+    let board_size = geometry.size();
+    let cover_start = board_size - base_start;
+
+    // Only lines where the value can go in 2 to `size` spaces can be base
+    // sets. The positions are a bitmask of the crossing lines
+    let mut lines = [0; Geometry::MAX_SIZE];
+    let mut positions = [0_u32; Geometry::MAX_SIZE];
+    let mut line_count = 0;
+
+    for line in 0..board_size {
+        let mut line_positions = 0_u32;
+
+        for (position, &index) in units[base_start + line].iter().enumerate() {
+            if board.possible_values(index).contains(value) {
+                line_positions |= 1 << position;
+            }
+        }
+
+        let space_count = line_positions.count_ones() as usize;
+        if space_count >= 2 && space_count <= size {
+            lines[line_count] = line;
+            positions[line_count] = line_positions;
+            line_count += 1;
+        }
+    }
+
+    if line_count < size {
+        return None;
+    }
+
+    let mut combination = [0, 1, 2, 3];
+
+    loop {
+        let base_lines = combination[..size].iter().map(|&position| lines[position]);
+        let cover_positions = combination[..size]
+            .iter()
+            .fold(0, |cover_positions, &position| {
+                cover_positions | positions[position]
+            });
+
+        if cover_positions.count_ones() as usize == size {
+            let cover_lines: Vec<usize> = (0..board_size)
+                .filter(|line| cover_positions & (1 << line) != 0)
+                .collect();
+
+            let mut eliminations = Vec::new();
+
+            for &cover_line in &cover_lines {
+                for (position, &index) in units[cover_start + cover_line].iter().enumerate() {
+                    if base_lines.clone().any(|base_line| base_line == position) {
+                        continue;
+                    }
+
+                    if board.possible_values(index).contains(value) {
+                        eliminations.push((geometry.point_of(index), value));
+                    }
+                }
+            }
+
+            if !eliminations.is_empty() {
+                return Some(Deduction {
+                    technique,
+                    pattern: Pattern::Fish {
+                        value,
+                        base_sets: base_lines
+                            .map(|line| geometry.unit(base_start + line))
+                            .collect(),
+                        cover_sets: cover_lines
+                            .into_iter()
+                            .map(|line| geometry.unit(cover_start + line))
+                            .collect(),
+                    },
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+
+        if !next_combination(&mut combination[..size], line_count) {
+            break;
+        }
+    }
+
+    return None;
+}
@@ -0,0 +1,114 @@
+//! Locked candidates, where a box and a row or column cross: pointing, and
+//! box/line reduction
+
+use crate::SudokuBoard;
+
+use super::{points_of, Deduction, Pattern, Technique};
+
+/// Finds a value that can only go in one row or column of a box. The value
+/// must be in that part of the line, so it can be removed from the rest of
+/// the line
+pub(crate) fn find_pointing(board: &SudokuBoard) -> Option<Deduction> {
+    let size = board.geometry().size();
+
+    for box_unit in size * 2..size * 3 {
+        // Rows, then columns
+        for line_kind in 0..2 {
+            if let Some(deduction) =
+                find_locked_candidates(board, box_unit, line_kind, Technique::Pointing)
+            {
+                return Some(deduction);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a value that can only go in one box of a row or column. The value
+/// must be in that part of the box, so it can be removed from the rest of
+/// the box
+pub(crate) fn find_box_line_reduction(board: &SudokuBoard) -> Option<Deduction> {
+    for line_unit in 0..board.geometry().size() * 2 {
+        if let Some(deduction) =
+            find_locked_candidates(board, line_unit, 2, Technique::BoxLineReduction)
+        {
+            return Some(deduction);
+        }
+    }
+
+    return None;
+}
+
+/// Looks for a value whose possible spaces in `unit` all share one other
+/// unit, of the kind at `other_kind` in `Geometry::units_of_cell` (0 for
+/// rows, 1 for columns, 2 for boxes). The value can then be removed from the rest of
+/// that other unit
+fn find_locked_candidates(
+    board: &SudokuBoard,
+    unit: usize,
+    other_kind: usize,
+    technique: Technique,
+) -> Option<Deduction> {
+    let geometry = board.geometry();
+    let units = geometry.units();
+
+    'values: for value in 1..=geometry.size() {
+        let mut other_unit = None;
+        let mut spaces = Vec::new();
+
+        for &index in &units[unit] {
+            if !board.possible_values(index).contains(value) {
+                continue;
+            }
+
+            let this_other_unit = geometry.units_of_cell(index)[other_kind];
+
+            match other_unit {
+                None => other_unit = Some(this_other_unit),
+                Some(other_unit) => {
+                    if other_unit != this_other_unit {
+                        continue 'values;
+                    }
+                }
+            }
+
+            spaces.push(index);
+        }
+
+        let other_unit = match other_unit {
+            Some(other_unit) => other_unit,
+            None => continue,
+        };
+
+        let eliminations: Vec<((usize, usize), usize)> = units[other_unit]
+            .iter()
+            .filter(|index| !units[unit].contains(index))
+            .filter(|&&index| board.possible_values(index).contains(value))
+            .map(|&index| (geometry.point_of(index), value))
+            .collect();
+
+        if !eliminations.is_empty() {
+            // The box is always the first unit in the pattern
+            let (box_unit, line_unit) = if other_kind == 2 {
+                (other_unit, unit)
+            } else {
+                (unit, other_unit)
+            };
+
+            return Some(Deduction {
+                technique,
+                pattern: Pattern::LockedCandidates {
+                    value,
+                    box_unit: geometry.unit(box_unit),
+                    line: geometry.unit(line_unit),
+                    spaces: points_of(board, spaces),
+                },
+                placements: Vec::new(),
+                eliminations,
+            });
+        }
+    }
+
+    return None;
+}
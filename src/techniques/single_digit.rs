@@ -0,0 +1,333 @@
+//! Patterns for a single value: simple coloring, multi-coloring, and
+//! X-Chains
+//!
+//! These are built from strong links. Two spaces are strongly linked for a
+//! value if they're the only spaces in a row, column, or box that can be
+//! that value, so one of them has to be it
+
+use std::collections::VecDeque;
+
+use crate::SudokuBoard;
+
+use super::{are_peers, points_of, Chain, ChainNode, Deduction, Link, Pattern, Technique};
+
+/// Finds a cluster of spaces joined by strong links, and colors them in
+/// two colors, alternating along each link. One color is all `value`, and
+/// the other has none of it. If two spaces of the same color are adjacent,
+/// that color can't be the value. Otherwise, any space adjacent to both
+/// colors can't be the value
+pub(crate) fn find_simple_coloring(board: &SudokuBoard) -> Option<Deduction> {
+    for value in 1..=board.geometry().size() {
+        let links = strong_links(board, value);
+
+        for colors in clusters(&links) {
+            for color in &colors {
+                if any_peers(board, color, color) {
+                    let eliminations = color
+                        .iter()
+                        .map(|&index| (board.geometry().point_of(index), value));
+
+                    return Some(coloring_deduction(
+                        board,
+                        Technique::SimpleColoring,
+                        value,
+                        &[&colors[0], &colors[1]],
+                        eliminations.collect(),
+                    ));
+                }
+            }
+
+            let eliminations: Vec<((usize, usize), usize)> = (0..board.geometry().spaces())
+                .filter(|&index| board.possible_values(index).contains(value))
+                .filter(|&index| {
+                    sees_any(board, index, &colors[0]) && sees_any(board, index, &colors[1])
+                })
+                .map(|index| (board.geometry().point_of(index), value))
+                .collect();
+
+            if !eliminations.is_empty() {
+                return Some(coloring_deduction(
+                    board,
+                    Technique::SimpleColoring,
+                    value,
+                    &[&colors[0], &colors[1]],
+                    eliminations,
+                ));
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds two clusters of colored spaces, where a color in one cluster is
+/// adjacent to a color in the other, so they can't both be `value`. Then
+/// one of their opposite colors has to be the value, and it can be removed
+/// from any space adjacent to both of those. If a color is adjacent to both
+/// colors of the other cluster, it can't be the value at all
+pub(crate) fn find_multi_coloring(board: &SudokuBoard) -> Option<Deduction> {
+    for value in 1..=board.geometry().size() {
+        let links = strong_links(board, value);
+        let clusters = clusters(&links);
+
+        for (first, first_colors) in clusters.iter().enumerate() {
+            for second_colors in &clusters[first + 1..] {
+                let all_colors = [
+                    &first_colors[0],
+                    &first_colors[1],
+                    &second_colors[0],
+                    &second_colors[1],
+                ];
+
+                for (color, other_color) in [(0, 2), (0, 3), (1, 2), (1, 3)] {
+                    if !any_peers(board, all_colors[color], all_colors[other_color]) {
+                        continue;
+                    }
+
+                    // The color is also adjacent to the other cluster's
+                    // opposite color
+                    let wrapped_color =
+                        if any_peers(board, all_colors[color], all_colors[other_color ^ 1]) {
+                            Some(color)
+                        } else if any_peers(board, all_colors[other_color], all_colors[color ^ 1]) {
+                            Some(other_color)
+                        } else {
+                            None
+                        };
+
+                    if let Some(wrapped_color) = wrapped_color {
+                        let eliminations = all_colors[wrapped_color]
+                            .iter()
+                            .map(|&index| (board.geometry().point_of(index), value));
+
+                        return Some(coloring_deduction(
+                            board,
+                            Technique::MultiColoring,
+                            value,
+                            &all_colors,
+                            eliminations.collect(),
+                        ));
+                    }
+
+                    let opposite_colors = [all_colors[color ^ 1], all_colors[other_color ^ 1]];
+                    let eliminations: Vec<((usize, usize), usize)> = (0..board.geometry().spaces())
+                        .filter(|&index| board.possible_values(index).contains(value))
+                        .filter(|&index| {
+                            sees_any(board, index, opposite_colors[0])
+                                && sees_any(board, index, opposite_colors[1])
+                        })
+                        .map(|index| (board.geometry().point_of(index), value))
+                        .collect();
+
+                    if !eliminations.is_empty() {
+                        return Some(coloring_deduction(
+                            board,
+                            Technique::MultiColoring,
+                            value,
+                            &all_colors,
+                            eliminations,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a chain of spaces for a value, with links alternating between
+/// strong and weak, that starts and ends with a strong link. If the start
+/// isn't the value, then following the links, the end has to be. So `value`
+/// can be removed from any space adjacent to both ends
+pub(crate) fn find_x_chain(board: &SudokuBoard) -> Option<Deduction> {
+    for value in 1..=board.geometry().size() {
+        let links = strong_links(board, value);
+
+        for start in 0..board.geometry().spaces() {
+            if links[start].is_empty() {
+                continue;
+            }
+
+            // Breadth first search, so the shortest chains are found first.
+            // Each space is visited as "off" (isn't the value), or "on"
+            // (is), stored as `index * 2 + on`
+            let mut previous = vec![usize::MAX; links.len() * 2];
+            let mut queue = VecDeque::new();
+
+            previous[start * 2] = start * 2;
+            queue.push_back(start * 2);
+
+            while let Some(state) = queue.pop_front() {
+                let (index, on) = (state / 2, state % 2 == 1);
+
+                // Off spaces follow strong links to on spaces, and on
+                // spaces follow weak links to off spaces
+                let next_spaces: Vec<usize> = if on {
+                    board
+                        .geometry()
+                        .peers(index)
+                        .iter()
+                        .copied()
+                        .filter(|&peer| board.possible_values(peer).contains(value))
+                        .collect()
+                } else {
+                    links[index].clone()
+                };
+
+                for next_index in next_spaces {
+                    let next_state = next_index * 2 + !on as usize;
+                    if previous[next_state] != usize::MAX {
+                        continue;
+                    }
+
+                    previous[next_state] = state;
+                    queue.push_back(next_state);
+
+                    if on || next_index == start {
+                        continue;
+                    }
+
+                    let mut chain = vec![next_index];
+                    let mut chain_state = state;
+                    while chain_state != start * 2 {
+                        chain.push(chain_state / 2);
+                        chain_state = previous[chain_state];
+                    }
+                    chain.push(start);
+
+                    // A single strong link is left to the easier techniques
+                    if chain.len() < 4 {
+                        continue;
+                    }
+
+                    let eliminations: Vec<((usize, usize), usize)> = board
+                        .geometry()
+                        .peers(start)
+                        .iter()
+                        .copied()
+                        .filter(|&peer| are_peers(board, peer, next_index))
+                        .filter(|&peer| board.possible_values(peer).contains(value))
+                        .map(|peer| (board.geometry().point_of(peer), value))
+                        .collect();
+
+                    if !eliminations.is_empty() {
+                        chain.reverse();
+
+                        return Some(Deduction {
+                            technique: Technique::XChain,
+                            pattern: Pattern::Chain(Chain {
+                                links: (1..chain.len())
+                                    .map(|link| {
+                                        if link % 2 == 1 {
+                                            Link::Strong
+                                        } else {
+                                            Link::Weak
+                                        }
+                                    })
+                                    .collect(),
+                                nodes: chain
+                                    .into_iter()
+                                    .map(|index| ChainNode {
+                                        spaces: vec![board.geometry().point_of(index)],
+                                        value,
+                                    })
+                                    .collect(),
+                            }),
+                            placements: Vec::new(),
+                            eliminations,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Returns the spaces each space is strongly linked to for `value`
+fn strong_links(board: &SudokuBoard, value: usize) -> Vec<Vec<usize>> {
+    let mut links = vec![Vec::new(); board.geometry().spaces()];
+
+    for unit in board.geometry().units() {
+        let mut spaces = unit
+            .iter()
+            .copied()
+            .filter(|&index| board.possible_values(index).contains(value));
+
+        if let (Some(first), Some(second), None) = (spaces.next(), spaces.next(), spaces.next()) {
+            // A row or column and a box can link the same two spaces
+            if !links[first].contains(&second) {
+                links[first].push(second);
+                links[second].push(first);
+            }
+        }
+    }
+
+    return links;
+}
+
+/// Splits spaces with strong links into clusters of linked spaces, and
+/// colors each cluster in two alternating colors
+fn clusters(links: &[Vec<usize>]) -> Vec<[Vec<usize>; 2]> {
+    let mut clusters = Vec::new();
+    let mut colored = vec![false; links.len()];
+
+    for start in 0..links.len() {
+        if colored[start] || links[start].is_empty() {
+            continue;
+        }
+
+        let mut colors = [Vec::new(), Vec::new()];
+        let mut to_color = vec![(start, 0)];
+        colored[start] = true;
+
+        while let Some((index, color)) = to_color.pop() {
+            colors[color].push(index);
+
+            for &linked in &links[index] {
+                if !colored[linked] {
+                    colored[linked] = true;
+                    to_color.push((linked, 1 - color));
+                }
+            }
+        }
+
+        clusters.push(colors);
+    }
+
+    return clusters;
+}
+
+/// Returns if any space in `first` is adjacent to any space in `second`
+fn any_peers(board: &SudokuBoard, first: &[usize], second: &[usize]) -> bool {
+    return first.iter().any(|&index| sees_any(board, index, second));
+}
+
+/// Returns if `index` is adjacent to any space in `spaces`
+fn sees_any(board: &SudokuBoard, index: usize, spaces: &[usize]) -> bool {
+    return spaces.iter().any(|&space| are_peers(board, index, space));
+}
+
+/// Makes a deduction from colored clusters
+fn coloring_deduction(
+    board: &SudokuBoard,
+    technique: Technique,
+    value: usize,
+    colors: &[&Vec<usize>],
+    eliminations: Vec<((usize, usize), usize)>,
+) -> Deduction {
+    return Deduction {
+        technique,
+        pattern: Pattern::Coloring {
+            value,
+            colors: colors
+                .iter()
+                .map(|color| points_of(board, color.iter().copied()))
+                .collect(),
+        },
+        placements: Vec::new(),
+        eliminations,
+    };
+}
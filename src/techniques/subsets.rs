@@ -0,0 +1,172 @@
+//! Naked and hidden subsets: pairs, triples, and quads
+
+use crate::{Candidates, Geometry, SudokuBoard, SudokuValue};
+
+use super::{next_combination, points_of, Deduction, Pattern, Technique};
+
+/// Finds `size` spaces in a row, column, or box that between them only
+/// have `size` possible values. Those values must go in those spaces, so
+/// they can be removed from every other space in the unit
+pub(crate) fn find_naked_subset(board: &SudokuBoard, size: usize) -> Option<Deduction> {
+    let technique = match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => Technique::NakedQuad,
+    };
+
+    let geometry = board.geometry();
+
+    for (unit_index, unit) in geometry.units().iter().enumerate() {
+        // Only unknown spaces with at most `size` possible values can be
+        // in the subset
+        let mut spaces = [0; Geometry::MAX_SIZE];
+        let mut space_count = 0;
+
+        for &index in unit {
+            if let SudokuValue::Unknown(possible_values) = board.get_space(index) {
+                if possible_values.len() <= size {
+                    spaces[space_count] = index;
+                    space_count += 1;
+                }
+            }
+        }
+
+        if space_count < size {
+            continue;
+        }
+
+        let mut combination = [0, 1, 2, 3];
+
+        loop {
+            let subset = combination[..size].iter().map(|&position| spaces[position]);
+            let values = subset.clone().fold(Candidates::default(), |values, index| {
+                values | board.possible_values(index)
+            });
+
+            if values.len() == size {
+                let mut eliminations = Vec::new();
+
+                for &index in unit {
+                    if subset.clone().any(|subset_index| subset_index == index) {
+                        continue;
+                    }
+
+                    for value in (board.possible_values(index) & values).iter() {
+                        eliminations.push((geometry.point_of(index), value));
+                    }
+                }
+
+                if !eliminations.is_empty() {
+                    return Some(Deduction {
+                        technique,
+                        pattern: Pattern::Subset {
+                            unit: geometry.unit(unit_index),
+                            spaces: points_of(board, subset),
+                            values: values.iter().collect(),
+                        },
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+
+            if !next_combination(&mut combination[..size], space_count) {
+                break;
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds `size` values in a row, column, or box that can only go in the
+/// same `size` spaces. Those spaces must have those values, so every other
+/// possible value can be removed from them
+pub(crate) fn find_hidden_subset(board: &SudokuBoard, size: usize) -> Option<Deduction> {
+    let technique = match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        _ => Technique::HiddenQuad,
+    };
+
+    let geometry = board.geometry();
+
+    for (unit_index, unit) in geometry.units().iter().enumerate() {
+        // The positions in the unit where each value could go, as a
+        // bitmask
+        let mut positions = [0_u32; Geometry::MAX_SIZE + 1];
+
+        for (position, &index) in unit.iter().enumerate() {
+            for value in board.possible_values(index).iter() {
+                positions[value] |= 1 << position;
+            }
+        }
+
+        // Only values that could go in 2 to `size` spaces can be in the
+        // subset. Values with one space are hidden singles, found by
+        // narrowing
+        let mut values = [0; Geometry::MAX_SIZE];
+        let mut value_count = 0;
+
+        for (value, value_positions) in positions.iter().enumerate() {
+            let space_count = value_positions.count_ones() as usize;
+
+            if space_count >= 2 && space_count <= size {
+                values[value_count] = value;
+                value_count += 1;
+            }
+        }
+
+        if value_count < size {
+            continue;
+        }
+
+        let mut combination = [0, 1, 2, 3];
+
+        loop {
+            let subset_values = combination[..size].iter().map(|&position| values[position]);
+            let subset_positions = subset_values.clone().fold(0, |subset_positions, value| {
+                subset_positions | positions[value]
+            });
+
+            if subset_positions.count_ones() as usize == size {
+                let mut value_set = Candidates::default();
+                for value in subset_values {
+                    value_set.insert(value);
+                }
+
+                let spaces: Vec<usize> = (0..unit.len())
+                    .filter(|position| subset_positions & (1 << position) != 0)
+                    .map(|position| unit[position])
+                    .collect();
+
+                let mut eliminations = Vec::new();
+
+                for &index in &spaces {
+                    for value in (board.possible_values(index) - value_set).iter() {
+                        eliminations.push((geometry.point_of(index), value));
+                    }
+                }
+
+                if !eliminations.is_empty() {
+                    return Some(Deduction {
+                        technique,
+                        pattern: Pattern::Subset {
+                            unit: geometry.unit(unit_index),
+                            spaces: points_of(board, spaces),
+                            values: value_set.iter().collect(),
+                        },
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+
+            if !next_combination(&mut combination[..size], value_count) {
+                break;
+            }
+        }
+    }
+
+    return None;
+}
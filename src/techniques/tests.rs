@@ -0,0 +1,940 @@
+//! Each technique on a hand-built board where its pattern is there, and
+//! one where it nearly is
+
+use std::sync::Arc;
+
+use crate::{Candidates, Geometry, SudokuBoard, SudokuValue, Unit};
+
+use super::{find, ChainNode, Link, Pattern, Technique};
+
+/// Makes a board from the possible values of each space, row by row,
+/// separated by whitespace. A space with one value is known, "." could be
+/// any value, and "-" followed by values could be anything but them
+///
+/// The board counts as checked, so the values are used as they are, and
+/// assumes the sudoku has one solution
+fn board_with_geometry(spaces: &str, geometry: Arc<Geometry>) -> SudokuBoard {
+    let size = geometry.size();
+
+    let values = spaces.split_whitespace().map(|space| {
+        let mut values = Candidates::default();
+
+        for digit in space.chars().filter(char::is_ascii_digit) {
+            values.insert(digit.to_digit(10).unwrap() as usize);
+        }
+
+        if space == "." || space.starts_with('-') {
+            values = Candidates::all(size) - values;
+        }
+
+        match values.only_value() {
+            Some(value) => return SudokuValue::Known(value as u8),
+            None => return SudokuValue::Unknown(values),
+        }
+    });
+
+    let mut board = SudokuBoard::from_values(values, geometry).unwrap();
+    board.initialized = true;
+    board.assume_unique_solution = true;
+
+    return board;
+}
+
+/// Makes a 9x9 board. See `board_with_geometry`
+fn board(spaces: &str) -> SudokuBoard {
+    return board_with_geometry(spaces, Geometry::standard());
+}
+
+/// Makes a 9x9 board where every space could be any value, apart from
+/// `value`, which can only go in the spaces marked with an "x". Other
+/// spaces are marked with a ".", and anything else is left out, so the
+/// boxes can be drawn
+fn value_board(value: usize, picture: &str) -> SudokuBoard {
+    let spaces: Vec<String> = picture
+        .chars()
+        .filter(|&character| character == 'x' || character == '.')
+        .map(|character| match character {
+            'x' => return ".".to_string(),
+            _ => return format!("-{}", value),
+        })
+        .collect();
+
+    return board(&spaces.join(" "));
+}
+
+/// Returns the possible values `technique` removes from the board. Panics
+/// if it finds nothing, or fills in a space
+fn eliminations(board: &SudokuBoard, technique: Technique) -> Vec<((usize, usize), usize)> {
+    let deduction = find(board, technique).expect("The technique should find its pattern");
+    assert!(deduction.placements.is_empty());
+
+    return deduction.eliminations;
+}
+
+/// Every space could be any value
+const OPEN: &str = "
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    ";
+
+/// A pivot that could be 1 or 2, and pincers that could be 1 or 3, and 2
+/// or 3. Whichever the pivot is, a pincer is 3, so (4, 4) can't be
+const XY_WING: &str = "
+    12  .   .   .   13  .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    23  .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    ";
+
+/// The same as `XY_WING`, but the pincers don't share a value
+const XY_WING_WITHOUT_A_SHARED_VALUE: &str = "
+    12  .   .   .   13  .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    24  .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    .   .   .   .   .   .   .   .   .
+    ";
+
+#[test]
+fn nothing_on_an_open_board() {
+    let board = board(OPEN);
+
+    for technique in Technique::ALL {
+        assert_eq!(find(&board, technique), None, "{}", technique);
+    }
+}
+
+#[test]
+fn pointing() {
+    let board = value_board(
+        1,
+        "
+        x x . | x x x | x x x
+        . . . | x x x | x x x
+        . . . | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    let expected: Vec<_> = (3..9).map(|column| ((0, column), 1)).collect();
+
+    assert_eq!(eliminations(&board, Technique::Pointing), expected);
+    assert_eq!(find(&board, Technique::BoxLineReduction), None);
+}
+
+#[test]
+fn box_line_reduction() {
+    let board = value_board(
+        1,
+        "
+        x x x | . . . | . . .
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    let expected: Vec<_> = [(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+        .into_iter()
+        .map(|point| (point, 1))
+        .collect();
+
+    assert_eq!(eliminations(&board, Technique::BoxLineReduction), expected);
+    assert_eq!(find(&board, Technique::Pointing), None);
+}
+
+/// 1 is in two rows of the first box, and every row and column has it in
+/// more than one box
+#[test]
+fn locked_candidates_need_one_line() {
+    let board = value_board(
+        1,
+        "
+        x . . | x x x | x x x
+        . x . | x x x | x x x
+        . . . | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    assert_eq!(find(&board, Technique::Pointing), None);
+    assert_eq!(find(&board, Technique::BoxLineReduction), None);
+}
+
+#[test]
+fn naked_pair() {
+    let board = board(
+        "
+        12  12  .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let deduction = find(&board, Technique::NakedPair).unwrap();
+    let expected: Vec<_> = (2..9)
+        .flat_map(|column| [((0, column), 1), ((0, column), 2)])
+        .collect();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::Subset {
+            unit: Unit::Row(0),
+            spaces: vec![(0, 0), (0, 1)],
+            values: vec![1, 2],
+        }
+    );
+    assert_eq!(deduction.eliminations, expected);
+}
+
+#[test]
+fn hidden_pair() {
+    let board = board(
+        "
+        .   .   -12 -12 -12 -12 -12 -12 -12
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let expected: Vec<_> = [(0, 0), (0, 1)]
+        .into_iter()
+        .flat_map(|point| (3..=9).map(move |value| (point, value)))
+        .collect();
+
+    assert_eq!(eliminations(&board, Technique::HiddenPair), expected);
+}
+
+/// Two spaces with three values between them, and 2 is in a third space
+/// of the row
+#[test]
+fn subsets_need_as_many_values_as_spaces() {
+    let naked = board(
+        "
+        12  123 .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+    let hidden = board(
+        "
+        .   .   -1  -12 -12 -12 -12 -12 -12
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    assert_eq!(find(&naked, Technique::NakedPair), None);
+    assert_eq!(find(&hidden, Technique::HiddenPair), None);
+}
+
+#[test]
+fn x_wing() {
+    let board = value_board(
+        1,
+        "
+        x . . | . x . | . . .
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x . . | . x . | . . .
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    let deduction = find(&board, Technique::XWing).unwrap();
+    let expected: Vec<_> = [0, 4]
+        .into_iter()
+        .flat_map(|column| {
+            [1, 2, 3, 5, 6, 7, 8]
+                .into_iter()
+                .map(move |row| ((row, column), 1))
+        })
+        .collect();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::Fish {
+            value: 1,
+            base_sets: vec![Unit::Row(0), Unit::Row(4)],
+            cover_sets: vec![Unit::Column(0), Unit::Column(4)],
+        }
+    );
+    assert_eq!(deduction.eliminations, expected);
+}
+
+#[test]
+fn swordfish() {
+    let board = value_board(
+        1,
+        "
+        x . . | . x . | . . .
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        . . . | . x . | . . x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x . . | . . . | . . x
+        x x x | x x x | x x x
+        ",
+    );
+
+    let expected: Vec<_> = [0, 4, 8]
+        .into_iter()
+        .flat_map(|column| {
+            [1, 2, 3, 5, 6, 8]
+                .into_iter()
+                .map(move |row| ((row, column), 1))
+        })
+        .collect();
+
+    assert_eq!(eliminations(&board, Technique::Swordfish), expected);
+    assert_eq!(find(&board, Technique::XWing), None);
+}
+
+/// The second row has 1 in a third column
+#[test]
+fn fish_need_the_same_columns() {
+    let board = value_board(
+        1,
+        "
+        x . . | . x . | . . .
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x . . | . x . | . . x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    assert_eq!(find(&board, Technique::XWing), None);
+    assert_eq!(find(&board, Technique::Swordfish), None);
+}
+
+/// The strong links for 1 go (0, 0), (0, 4), (4, 4), (4, 1), so (0, 0)
+/// and (4, 4) are one color, and (0, 4) and (4, 1) the other
+#[test]
+fn simple_coloring() {
+    let board = value_board(
+        1,
+        "
+        x . . | . x . | . . .
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        ------+-------+------
+        x x x | x . x | x x x
+        . x . | . x . | . . .
+        x x x | x . x | x x x
+        ------+-------+------
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        ",
+    );
+
+    let deduction = find(&board, Technique::SimpleColoring).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::Coloring {
+            value: 1,
+            colors: vec![vec![(0, 0), (4, 4)], vec![(0, 4), (4, 1)]],
+        }
+    );
+    assert_eq!(
+        deduction.eliminations,
+        vec![((1, 1), 1), ((2, 1), 1), ((3, 0), 1), ((5, 0), 1)]
+    );
+}
+
+/// Two clusters, one on the first row and one down the second column.
+/// (0, 0) and (1, 1) share a box, so (0, 6) or (6, 1) is 1
+#[test]
+fn multi_coloring() {
+    let board = value_board(
+        1,
+        "
+        x . . | . . . | x . .
+        x x x | x x x | x x x
+        x . x | x x x | x x x
+        ------+-------+------
+        x . x | x x x | x x x
+        x . x | x x x | x x x
+        x . x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x . x | x x x | x x x
+        x . x | x x x | x x x
+        ",
+    );
+
+    assert_eq!(
+        eliminations(&board, Technique::MultiColoring),
+        vec![((6, 6), 1)]
+    );
+    assert_eq!(find(&board, Technique::SimpleColoring), None);
+}
+
+/// Strong links on the first and sixth rows, joined by a weak link down
+/// the seventh column
+#[test]
+fn x_chain() {
+    let board = value_board(
+        1,
+        "
+        x . . | . . . | x . .
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        . . x | . . . | x . .
+        ------+-------+------
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        x x x | x x x | x x x
+        ",
+    );
+
+    assert_eq!(
+        eliminations(&board, Technique::XChain),
+        vec![((1, 2), 1), ((2, 2), 1), ((3, 0), 1), ((4, 0), 1)]
+    );
+}
+
+/// Like `simple_coloring`, but the last link ends at (4, 8), and nothing
+/// is adjacent to both colors
+#[test]
+fn coloring_needs_a_space_adjacent_to_both_colors() {
+    let board = value_board(
+        1,
+        "
+        x . . | . x . | . . .
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        ------+-------+------
+        x x x | x . x | x x x
+        . . . | . x . | . . x
+        x x x | x . x | x x x
+        ------+-------+------
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        x x x | x . x | x x x
+        ",
+    );
+
+    assert_eq!(find(&board, Technique::SimpleColoring), None);
+    assert_eq!(find(&board, Technique::MultiColoring), None);
+    assert_eq!(find(&board, Technique::XChain), None);
+}
+
+#[test]
+fn xy_wing() {
+    let board = board(XY_WING);
+
+    let deduction = find(&board, Technique::XYWing).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::Wing {
+            value: 3,
+            pivots: vec![(0, 0)],
+            pincers: vec![(0, 4), (4, 0)],
+        }
+    );
+    assert_eq!(deduction.eliminations, vec![((4, 4), 3)]);
+}
+
+#[test]
+fn xyz_wing() {
+    let board = board(
+        "
+        123 13  .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        23  .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    assert_eq!(
+        eliminations(&board, Technique::XYZWing),
+        vec![((1, 0), 3), ((2, 0), 3)]
+    );
+    assert_eq!(find(&board, Technique::XYWing), None);
+}
+
+/// 2 can only go in (8, 0) and (8, 4) on the last row
+#[test]
+fn w_wing() {
+    let board = board(
+        "
+        12  .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   12  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   -2  -2  -2  .   -2  -2  -2  -2
+        ",
+    );
+
+    assert_eq!(
+        eliminations(&board, Technique::WWing),
+        vec![((0, 4), 1), ((4, 0), 1)]
+    );
+}
+
+/// The XY-Wing's pincers don't share a value, and the W-Wing's last row
+/// has 2 in a third space
+#[test]
+fn wings_need_a_shared_value() {
+    let xy_wing = board(XY_WING_WITHOUT_A_SHARED_VALUE);
+    let w_wing = board(
+        "
+        12  .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   12  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   -2  -2  -2  .   -2  -2  -2  .
+        ",
+    );
+
+    for technique in [Technique::XYWing, Technique::XYZWing, Technique::WWing] {
+        assert_eq!(find(&xy_wing, technique), None);
+        assert_eq!(find(&w_wing, technique), None);
+    }
+}
+
+#[test]
+fn unique_rectangle_type_1() {
+    let board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        12  .   .   1234 .  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let deduction = find(&board, Technique::UniqueRectangleType1).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::UniqueRectangle {
+            spaces: vec![(0, 0), (0, 3), (1, 0), (1, 3)],
+            values: vec![1, 2],
+        }
+    );
+    assert_eq!(deduction.eliminations, vec![((1, 3), 1), ((1, 3), 2)]);
+}
+
+/// The bottom corners both have an extra 3, so one of them is 3
+#[test]
+fn unique_rectangle_type_2() {
+    let board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        123 .   .   123 .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let expected: Vec<_> = [1, 2, 4, 5, 6, 7, 8]
+        .into_iter()
+        .map(|column| ((1, column), 3))
+        .collect();
+
+    assert_eq!(
+        eliminations(&board, Technique::UniqueRectangleType2),
+        expected
+    );
+}
+
+/// The extra 3 and 4 of the bottom corners make a naked pair with (1, 6)
+#[test]
+fn unique_rectangle_type_3() {
+    let board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        123 .   .   124 .   .   34  .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let expected: Vec<_> = [1, 2, 4, 5, 7, 8]
+        .into_iter()
+        .flat_map(|column| [((1, column), 3), ((1, column), 4)])
+        .collect();
+
+    assert_eq!(
+        eliminations(&board, Technique::UniqueRectangleType3),
+        expected
+    );
+}
+
+/// 1 can only go in the bottom corners on their row, so neither is 2
+#[test]
+fn unique_rectangle_type_4() {
+    let board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        123 -1  -1  1234 -1 -1  -1  -1  -1
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    assert_eq!(
+        eliminations(&board, Technique::UniqueRectangleType4),
+        vec![((1, 0), 2), ((1, 3), 2)]
+    );
+}
+
+/// The corners are in four boxes, so swapping them could break a box
+#[test]
+fn unique_rectangles_need_two_boxes() {
+    let board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        12  .   .   1234 .  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    assert_eq!(find(&board, Technique::UniqueRectangleType1), None);
+}
+
+#[test]
+fn unique_rectangles_need_a_unique_solution() {
+    let mut board = board(
+        "
+        12  .   .   12  .   .   .   .   .
+        12  .   .   1234 .  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+    board.assume_unique_solution = false;
+
+    assert_eq!(find(&board, Technique::UniqueRectangleType1), None);
+}
+
+#[test]
+fn bug_plus_one() {
+    let board = board(
+        "
+        4   3   9   8   1   7   5   6   2
+        1   5   8   9   6   2   4   7   3
+        6   2   7   5   4   3   9   18  18
+        59  7   2   1   3   4   6   58  589
+        3   8   4   7   59  6   1   2   59
+        59  6   1   2   8   59  7   3   4
+        8   4   5   6   2   19  3   19  7
+        2   9   6   3   7   15  8   4   15
+        7   1   3   4   59  8   2   59  6
+        ",
+    );
+
+    let deduction = find(&board, Technique::BugPlusOne).unwrap();
+
+    assert_eq!(deduction.pattern, Pattern::Bug { space: (3, 8) });
+    assert_eq!(deduction.placements, vec![((3, 8), 5)]);
+    assert!(deduction.eliminations.is_empty());
+}
+
+/// Every space has two possible values but one, and 5 is in each unit of
+/// that space three times, but 1 is in its row four times. So it isn't a
+/// grave, and 5 is wrong
+#[test]
+fn bug_plus_one_needs_every_unit_to_pair_up() {
+    let board = board_with_geometry(
+        "
+        2   6   4   1   3   5
+        3   5   1   46  2   46
+        16  3   5   26  4   12
+        16  4   2   56  15  3
+        15  12  6   235 15  14
+        4   12  3   25  6   12
+        ",
+        Arc::new(Geometry::new(3, 2).unwrap()),
+    );
+
+    assert_eq!(find(&board, Technique::BugPlusOne), None);
+}
+
+#[test]
+fn alternating_inference_chain() {
+    let board = board(XY_WING);
+
+    let deduction = find(&board, Technique::AlternatingInferenceChain).unwrap();
+    let Pattern::Chain(chain) = deduction.pattern else {
+        panic!("Expected a chain");
+    };
+
+    assert_eq!(chain.links.first(), Some(&Link::Strong));
+    assert_eq!(chain.links.last(), Some(&Link::Strong));
+    assert_eq!(deduction.eliminations, vec![((4, 4), 3)]);
+}
+
+/// Both values of the pivot lead to a pincer being 3
+#[test]
+fn cell_forcing_chain() {
+    let board = board(XY_WING);
+
+    let deduction = find(&board, Technique::CellForcingChain).unwrap();
+    let Pattern::ForcingChains { chains } = deduction.pattern else {
+        panic!("Expected forcing chains");
+    };
+
+    let starts: Vec<_> = chains.iter().map(|chain| chain.nodes[0].clone()).collect();
+
+    assert_eq!(
+        starts,
+        vec![
+            ChainNode {
+                spaces: vec![(0, 0)],
+                value: 1
+            },
+            ChainNode {
+                spaces: vec![(0, 0)],
+                value: 2
+            },
+        ]
+    );
+    assert_eq!(deduction.eliminations, vec![((4, 4), 3)]);
+}
+
+/// Wherever 1 goes in the first row, either it's (0, 4), so the pivot is
+/// 2 and (4, 0) is 3, or it isn't, so (0, 4) is 3
+#[test]
+fn unit_forcing_chain() {
+    let board = board(XY_WING);
+
+    assert_eq!(
+        eliminations(&board, Technique::UnitForcingChain),
+        vec![((4, 4), 3)]
+    );
+}
+
+#[test]
+fn chains_need_the_same_conclusion() {
+    let board = board(XY_WING_WITHOUT_A_SHARED_VALUE);
+
+    for technique in [
+        Technique::XChain,
+        Technique::AlternatingInferenceChain,
+        Technique::CellForcingChain,
+        Technique::UnitForcingChain,
+    ] {
+        assert_eq!(find(&board, technique), None);
+    }
+}
+
+/// (0, 0) is a set on its own, and (4, 0) and (4, 4) a set with 1, 2 and
+/// 3. 1 is the restricted common, so one of them has 2
+#[test]
+fn als_xz() {
+    let board = board(
+        "
+        12  .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        13  .   .   .   23  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let deduction = find(&board, Technique::AlsXz).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::AlmostLockedSets {
+            sets: vec![vec![(0, 0)], vec![(4, 0), (4, 4)]],
+            restricted_commons: vec![1],
+            value: 2,
+        }
+    );
+    assert_eq!(deduction.eliminations, vec![((0, 4), 2)]);
+}
+
+#[test]
+fn als_xy_wing() {
+    let board = board(XY_WING);
+
+    let deduction = find(&board, Technique::AlsXyWing).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::AlmostLockedSets {
+            sets: vec![vec![(0, 4)], vec![(4, 0)], vec![(0, 0)]],
+            restricted_commons: vec![1, 2],
+            value: 3,
+        }
+    );
+    assert_eq!(deduction.eliminations, vec![((4, 4), 3)]);
+}
+
+/// The stem (4, 4) is 1 or 2, and each has a petal with 3
+#[test]
+fn death_blossom() {
+    let board = board(
+        "
+        .   .   .   .   23  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        13  .   .   .   12  .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        .   .   .   .   .   .   .   .   .
+        ",
+    );
+
+    let deduction = find(&board, Technique::DeathBlossom).unwrap();
+
+    assert_eq!(
+        deduction.pattern,
+        Pattern::DeathBlossom {
+            stem: (4, 4),
+            petals: vec![vec![(4, 0)], vec![(0, 4)]],
+            value: 3,
+        }
+    );
+    assert_eq!(deduction.eliminations, vec![((0, 0), 3)]);
+}
+
+#[test]
+fn almost_locked_sets_need_a_shared_value() {
+    let board = board(XY_WING_WITHOUT_A_SHARED_VALUE);
+
+    for technique in [
+        Technique::AlsXz,
+        Technique::AlsXyWing,
+        Technique::DeathBlossom,
+    ] {
+        assert_eq!(find(&board, technique), None);
+    }
+}
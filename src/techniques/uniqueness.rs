@@ -0,0 +1,342 @@
+//! Techniques that only work if the sudoku has one solution: unique
+//! rectangles and BUG+1
+//!
+//! These look for patterns that, if left as they are, would let the sudoku
+//! be solved two ways. So something has to break the pattern
+
+use crate::{Candidates, Geometry, SudokuBoard};
+
+use super::{next_combination, points_of, Deduction, Pattern, Technique};
+
+/// Finds four spaces in the corners of a rectangle, across two boxes, that
+/// could all be the same two values. If they were only those two, they
+/// could be swapped, so the sudoku would have two solutions. The type
+/// picks how the pattern is broken:
+///
+/// 1. Three corners are just the pair, so the pair can be removed from the
+///    fourth
+/// 2. Two corners on a side have one extra value. One of them has to be
+///    it, so it can be removed from spaces adjacent to both
+/// 3. The extra values of two corners on a side act like one space in a
+///    naked subset
+/// 4. One of the pair can only go in the two corners on a side of a unit,
+///    so the other value of the pair can be removed from them
+pub(crate) fn find_unique_rectangle(
+    board: &SudokuBoard,
+    rectangle_type: usize,
+) -> Option<Deduction> {
+    let technique = match rectangle_type {
+        1 => Technique::UniqueRectangleType1,
+        2 => Technique::UniqueRectangleType2,
+        3 => Technique::UniqueRectangleType3,
+        _ => Technique::UniqueRectangleType4,
+    };
+
+    let geometry = board.geometry();
+    let size = geometry.size();
+
+    for top in 0..size {
+        for bottom in top + 1..size {
+            for left in 0..size {
+                for right in left + 1..size {
+                    let corners = [
+                        geometry.index_of((top, left)),
+                        geometry.index_of((top, right)),
+                        geometry.index_of((bottom, left)),
+                        geometry.index_of((bottom, right)),
+                    ];
+
+                    // The rectangle has to cover exactly two boxes
+                    let same_box_row =
+                        top / geometry.box_height() == bottom / geometry.box_height();
+                    let same_box_column =
+                        left / geometry.box_width() == right / geometry.box_width();
+
+                    if same_box_row == same_box_column {
+                        continue;
+                    }
+
+                    let shared = corners
+                        .iter()
+                        .fold(Candidates::all(size), |shared, &index| {
+                            shared & board.possible_values(index)
+                        });
+
+                    for first in shared.iter() {
+                        for second in shared.iter().filter(|&second| second > first) {
+                            let mut pair = Candidates::default();
+                            pair.insert(first);
+                            pair.insert(second);
+
+                            let eliminations = match rectangle_type {
+                                1 => type_1_eliminations(board, &corners, pair),
+                                _ => match roof(board, &corners, pair) {
+                                    Some(roof) => match rectangle_type {
+                                        2 => type_2_eliminations(board, roof, pair),
+                                        3 => type_3_eliminations(board, roof, pair),
+                                        _ => type_4_eliminations(board, roof, pair),
+                                    },
+                                    None => Vec::new(),
+                                },
+                            };
+
+                            if !eliminations.is_empty() {
+                                return Some(Deduction {
+                                    technique,
+                                    pattern: Pattern::UniqueRectangle {
+                                        spaces: points_of(board, corners),
+                                        values: vec![first, second],
+                                    },
+                                    placements: Vec::new(),
+                                    eliminations,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a board where every unknown space has two possible values, except
+/// one with three, and each unit has its possible values in two spaces
+/// each, apart from one extra. If that space was one of the values that's
+/// in its row twice, every space could swap between its two values, giving
+/// two solutions. So it has to be the value that's in its row three times
+pub(crate) fn find_bug_plus_one(board: &SudokuBoard) -> Option<Deduction> {
+    let mut bug_space = None;
+
+    for index in 0..board.geometry().spaces() {
+        match board.possible_values(index).len() {
+            0 | 2 => {}
+            3 if bug_space.is_none() => bug_space = Some(index),
+            _ => return None,
+        }
+    }
+
+    let bug_space = bug_space?;
+
+    // The value has to be there three times in each unit of the space
+    let value = board.possible_values(bug_space).iter().find(|&value| {
+        board
+            .geometry()
+            .units_of_cell(bug_space)
+            .iter()
+            .all(|&unit| {
+                board.geometry().units()[unit]
+                    .iter()
+                    .filter(|&&index| board.possible_values(index).contains(value))
+                    .count()
+                    == 3
+            })
+    })?;
+
+    // Without that value, it has to be a real grave: each value that's
+    // left in a unit is in exactly two of its spaces. Otherwise the spaces
+    // couldn't all swap, so there's no second solution to rule out
+    let mut bug_values = board.possible_values(bug_space);
+    bug_values.remove(value);
+
+    for unit in board.geometry().units() {
+        let mut counts = [0; Geometry::MAX_SIZE + 1];
+
+        for &index in unit {
+            let values = if index == bug_space {
+                bug_values
+            } else {
+                board.possible_values(index)
+            };
+
+            for other in values.iter() {
+                counts[other] += 1;
+            }
+        }
+
+        if counts.iter().any(|&count| count != 0 && count != 2) {
+            return None;
+        }
+    }
+
+    return Some(Deduction {
+        technique: Technique::BugPlusOne,
+        pattern: Pattern::Bug {
+            space: board.geometry().point_of(bug_space),
+        },
+        placements: vec![(board.geometry().point_of(bug_space), value)],
+        eliminations: Vec::new(),
+    });
+}
+
+/// Three corners are just the pair, so the fourth can't be either of them
+fn type_1_eliminations(
+    board: &SudokuBoard,
+    corners: &[usize; 4],
+    pair: Candidates,
+) -> Vec<((usize, usize), usize)> {
+    let mut others = corners
+        .iter()
+        .copied()
+        .filter(|&index| board.possible_values(index) != pair);
+
+    return match (others.next(), others.next()) {
+        (Some(index), None) => pair
+            .iter()
+            .map(|value| (board.geometry().point_of(index), value))
+            .collect(),
+        _ => Vec::new(),
+    };
+}
+
+/// The two corners on a side with more than the pair, when the other two
+/// are just the pair. Returns the corners, and the units they share
+fn roof(
+    board: &SudokuBoard,
+    corners: &[usize; 4],
+    pair: Candidates,
+) -> Option<([usize; 2], Vec<usize>)> {
+    let roof: Vec<usize> = corners
+        .iter()
+        .copied()
+        .filter(|&index| board.possible_values(index) != pair)
+        .collect();
+
+    if roof.len() != 2 {
+        return None;
+    }
+
+    let geometry = board.geometry();
+    let units: Vec<usize> = geometry
+        .units_of_cell(roof[0])
+        .iter()
+        .copied()
+        .filter(|unit| geometry.units_of_cell(roof[1]).contains(unit))
+        .collect();
+
+    // Corners on opposite sides of the rectangle don't share a unit
+    if units.is_empty() {
+        return None;
+    }
+
+    return Some(([roof[0], roof[1]], units));
+}
+
+/// Both roof corners have the same one extra value, so one of them is it
+fn type_2_eliminations(
+    board: &SudokuBoard,
+    (roof, units): ([usize; 2], Vec<usize>),
+    pair: Candidates,
+) -> Vec<((usize, usize), usize)> {
+    let extra = board.possible_values(roof[0]) - pair;
+    if extra.len() != 1 || board.possible_values(roof[1]) - pair != extra {
+        return Vec::new();
+    }
+
+    let value = extra.only_value().unwrap();
+    let mut eliminations = Vec::new();
+
+    for &unit in &units {
+        for &index in &board.geometry().units()[unit] {
+            let elimination = (board.geometry().point_of(index), value);
+
+            if !roof.contains(&index)
+                && board.possible_values(index).contains(value)
+                && !eliminations.contains(&elimination)
+            {
+                eliminations.push(elimination);
+            }
+        }
+    }
+
+    return eliminations;
+}
+
+/// One of the roof corners has to be one of their extra values, so
+/// together they act like a single space with those values, which can be
+/// part of a naked subset in a unit they share
+fn type_3_eliminations(
+    board: &SudokuBoard,
+    (roof, units): ([usize; 2], Vec<usize>),
+    pair: Candidates,
+) -> Vec<((usize, usize), usize)> {
+    let extra = (board.possible_values(roof[0]) | board.possible_values(roof[1])) - pair;
+
+    for &unit in &units {
+        // The other unknown spaces in the unit
+        let spaces: Vec<usize> = board.geometry().units()[unit]
+            .iter()
+            .copied()
+            .filter(|index| !roof.contains(index))
+            .filter(|&index| board.possible_values(index).len() > 0)
+            .collect();
+
+        for size in 1..=3 {
+            if spaces.len() <= size {
+                break;
+            }
+
+            let mut combination = [0, 1, 2];
+
+            loop {
+                let subset = combination[..size].iter().map(|&position| spaces[position]);
+                let values = subset
+                    .clone()
+                    .fold(extra, |values, index| values | board.possible_values(index));
+
+                if values.len() == size + 1 {
+                    let eliminations: Vec<((usize, usize), usize)> = spaces
+                        .iter()
+                        .copied()
+                        .filter(|&index| !subset.clone().any(|subset_index| subset_index == index))
+                        .flat_map(|index| {
+                            (board.possible_values(index) & values)
+                                .iter()
+                                .map(move |value| (board.geometry().point_of(index), value))
+                        })
+                        .collect();
+
+                    if !eliminations.is_empty() {
+                        return eliminations;
+                    }
+                }
+
+                if !next_combination(&mut combination[..size], spaces.len()) {
+                    break;
+                }
+            }
+        }
+    }
+
+    return Vec::new();
+}
+
+/// One of the pair can only go in the roof corners of a unit, so one of
+/// them is it. The other can't be the other value of the pair, or the
+/// rectangle would be left with just the pair
+fn type_4_eliminations(
+    board: &SudokuBoard,
+    (roof, units): ([usize; 2], Vec<usize>),
+    pair: Candidates,
+) -> Vec<((usize, usize), usize)> {
+    for &unit in &units {
+        for value in pair.iter() {
+            let locked = board.geometry().units()[unit]
+                .iter()
+                .filter(|&&index| board.possible_values(index).contains(value))
+                .all(|index| roof.contains(index));
+
+            if locked {
+                let other_value = pair.iter().find(|&other| other != value).unwrap();
+
+                return roof
+                    .iter()
+                    .map(|&index| (board.geometry().point_of(index), other_value))
+                    .collect();
+            }
+        }
+    }
+
+    return Vec::new();
+}
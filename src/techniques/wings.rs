@@ -0,0 +1,194 @@
+//! Wings: XY-Wing, XYZ-Wing, and W-Wing
+//!
+//! Each wing has two pincer spaces, one of which must be some value. That
+//! value can be removed from any space adjacent to both pincers (and for
+//! an XYZ-Wing, the pivot too)
+
+use crate::SudokuBoard;
+
+use super::{are_peers, points_of, Deduction, Pattern, Technique};
+
+/// Finds a pivot space with two possible values, xy, and two adjacent
+/// pincers with xz and yz. Whichever value the pivot is, one of the
+/// pincers is z
+pub(crate) fn find_xy_wing(board: &SudokuBoard) -> Option<Deduction> {
+    for pivot in 0..board.geometry().spaces() {
+        let peers = board.geometry().peers(pivot);
+        let pivot_values = board.possible_values(pivot);
+        if pivot_values.len() != 2 {
+            continue;
+        }
+
+        for (first, &pincer_a) in peers.iter().enumerate() {
+            let a_values = board.possible_values(pincer_a);
+            if a_values.len() != 2 || (a_values & pivot_values).len() != 1 {
+                continue;
+            }
+
+            for &pincer_b in &peers[first + 1..] {
+                let b_values = board.possible_values(pincer_b);
+                let shared = a_values & b_values;
+
+                // b has the other pivot value, and the same z as a
+                if b_values.len() != 2
+                    || (b_values & pivot_values).len() != 1
+                    || shared.len() != 1
+                    || (shared & pivot_values).len() != 0
+                {
+                    continue;
+                }
+
+                let value = shared.only_value().unwrap();
+
+                if let Some(deduction) = wing_deduction(
+                    board,
+                    Technique::XYWing,
+                    vec![pivot],
+                    [pincer_a, pincer_b],
+                    value,
+                ) {
+                    return Some(deduction);
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds a pivot space with three possible values, xyz, and two adjacent
+/// pincers with xz and yz. One of the pivot and pincers must be z
+pub(crate) fn find_xyz_wing(board: &SudokuBoard) -> Option<Deduction> {
+    for pivot in 0..board.geometry().spaces() {
+        let peers = board.geometry().peers(pivot);
+        let pivot_values = board.possible_values(pivot);
+        if pivot_values.len() != 3 {
+            continue;
+        }
+
+        for (first, &pincer_a) in peers.iter().enumerate() {
+            let a_values = board.possible_values(pincer_a);
+            if a_values.len() != 2 || (a_values - pivot_values).len() != 0 {
+                continue;
+            }
+
+            for &pincer_b in &peers[first + 1..] {
+                let b_values = board.possible_values(pincer_b);
+                if b_values.len() != 2
+                    || (b_values - pivot_values).len() != 0
+                    || (a_values & b_values).len() != 1
+                {
+                    continue;
+                }
+
+                let value = (a_values & b_values).only_value().unwrap();
+
+                if let Some(deduction) = wing_deduction(
+                    board,
+                    Technique::XYZWing,
+                    vec![pivot],
+                    [pincer_a, pincer_b],
+                    value,
+                ) {
+                    return Some(deduction);
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds two pincers with the same two possible values, xz, that aren't
+/// adjacent, and a unit where x can only go in two spaces, one adjacent to
+/// each pincer. Those two spaces are the pivots. One of the pivots is x,
+/// so the pincer next to it isn't, and has to be z
+pub(crate) fn find_w_wing(board: &SudokuBoard) -> Option<Deduction> {
+    let spaces = board.geometry().spaces();
+
+    for pincer_a in 0..spaces {
+        let values = board.possible_values(pincer_a);
+        if values.len() != 2 {
+            continue;
+        }
+
+        for pincer_b in pincer_a + 1..spaces {
+            if board.possible_values(pincer_b) != values || are_peers(board, pincer_a, pincer_b) {
+                continue;
+            }
+
+            for link_value in values.iter() {
+                let value = values.iter().find(|&value| value != link_value).unwrap();
+
+                for unit in board.geometry().units() {
+                    let mut link = unit
+                        .iter()
+                        .copied()
+                        .filter(|&index| board.possible_values(index).contains(link_value));
+
+                    let (pivot_a, pivot_b) = match (link.next(), link.next(), link.next()) {
+                        (Some(first), Some(second), None) => (first, second),
+                        _ => continue,
+                    };
+
+                    // Match up each pivot with the pincer it's adjacent to
+                    let pivots = if are_peers(board, pivot_a, pincer_a)
+                        && are_peers(board, pivot_b, pincer_b)
+                    {
+                        vec![pivot_a, pivot_b]
+                    } else if are_peers(board, pivot_b, pincer_a)
+                        && are_peers(board, pivot_a, pincer_b)
+                    {
+                        vec![pivot_b, pivot_a]
+                    } else {
+                        continue;
+                    };
+
+                    if let Some(deduction) =
+                        wing_deduction(board, Technique::WWing, pivots, [pincer_a, pincer_b], value)
+                    {
+                        return Some(deduction);
+                    }
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Removes `value` from every space adjacent to both pincers. An XYZ-Wing's
+/// pivot can be the value too, so the spaces must be adjacent to it as well
+fn wing_deduction(
+    board: &SudokuBoard,
+    technique: Technique,
+    pivots: Vec<usize>,
+    pincers: [usize; 2],
+    value: usize,
+) -> Option<Deduction> {
+    let eliminations: Vec<((usize, usize), usize)> = board
+        .geometry()
+        .peers(pincers[0])
+        .iter()
+        .copied()
+        .filter(|&index| are_peers(board, index, pincers[1]))
+        .filter(|&index| technique != Technique::XYZWing || are_peers(board, index, pivots[0]))
+        .filter(|&index| board.possible_values(index).contains(value))
+        .map(|index| (board.geometry().point_of(index), value))
+        .collect();
+
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    return Some(Deduction {
+        technique,
+        pattern: Pattern::Wing {
+            value,
+            pivots: points_of(board, pivots),
+            pincers: points_of(board, pincers),
+        },
+        placements: Vec::new(),
+        eliminations,
+    });
+}
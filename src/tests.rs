@@ -0,0 +1,273 @@
+//! Solving, counting and checking whole boards
+
+use crate::{
+    Deduction, Geometry, Pattern, SolutionCount, SudokuBoard, SudokuError, Technique, TechniqueSet,
+    Unit,
+};
+
+/// Has 10 solutions, with unique rectangles between them
+const TEN_SOLUTIONS: &str =
+    "504008010600005040100302060050701003020800701010000806060030204007010600305080070";
+
+#[test]
+fn counting_without_solutions() {
+    let board = SudokuBoard::from_file("boards/unsolveable").unwrap();
+
+    for limit in [0, 1, 2, 10] {
+        assert_eq!(board.count_solutions(limit), SolutionCount::Exactly(0));
+    }
+
+    assert!(!board.has_unique_solution());
+}
+
+#[test]
+fn counting_one_solution() {
+    let board = SudokuBoard::from_file("boards/easy").unwrap();
+
+    assert_eq!(board.count_solutions(0), SolutionCount::AtLeast(0));
+    assert_eq!(board.count_solutions(1), SolutionCount::AtLeast(1));
+    assert_eq!(board.count_solutions(2), SolutionCount::Exactly(1));
+    assert_eq!(board.count_solutions(10), SolutionCount::Exactly(1));
+    assert!(board.has_unique_solution());
+}
+
+#[test]
+fn counting_many_solutions() {
+    let board: SudokuBoard = TEN_SOLUTIONS.parse().unwrap();
+
+    assert_eq!(board.count_solutions(0), SolutionCount::AtLeast(0));
+    assert_eq!(board.count_solutions(2), SolutionCount::AtLeast(2));
+    assert_eq!(board.count_solutions(10), SolutionCount::AtLeast(10));
+    assert_eq!(board.count_solutions(11), SolutionCount::Exactly(10));
+    assert!(!board.has_unique_solution());
+
+    let blank: SudokuBoard = "0".repeat(81).parse().unwrap();
+    assert_eq!(blank.count_solutions(50), SolutionCount::AtLeast(50));
+}
+
+#[test]
+fn counting_ignores_the_unique_solution_assumption() {
+    let mut board: SudokuBoard = TEN_SOLUTIONS.parse().unwrap();
+    board.set_techniques(TechniqueSet::all()).unwrap();
+    board.set_assume_unique_solution(true);
+
+    assert_eq!(board.count_solutions(100), SolutionCount::Exactly(10));
+    assert_eq!(board.solutions().count(), 10);
+    assert!(!board.has_unique_solution());
+    assert!(board.assumes_unique_solution());
+}
+
+/// Removes `value` from the space at `point`, as if a naked pair in its
+/// row had ruled it out
+fn elimination(point: (usize, usize), value: usize) -> Deduction {
+    return Deduction {
+        technique: Technique::NakedPair,
+        pattern: Pattern::Subset {
+            unit: Unit::Row(0),
+            spaces: vec![(0, 0), (0, 1)],
+            values: vec![1, 2],
+        },
+        placements: Vec::new(),
+        eliminations: vec![(point, value)],
+    };
+}
+
+#[test]
+fn deductions_off_the_board() {
+    let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+
+    // (0, 12) would wrap round to (1, 3)
+    assert!(matches!(
+        board.apply_deduction(&elimination((0, 12), 1)),
+        Result::Err(SudokuError::OffBoard { point: (0, 12) })
+    ));
+    assert_eq!(board.candidates((1, 3)), (1..=9).collect::<Vec<usize>>());
+}
+
+#[test]
+fn deductions_with_values_off_the_board() {
+    let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+
+    for value in [0, 10, 32, 100] {
+        assert!(matches!(
+            board.apply_deduction(&elimination((0, 2), value)),
+            Result::Err(SudokuError::ValueNotPossible { point: (0, 2), value: v }) if v == value
+        ));
+    }
+
+    board.apply_deduction(&elimination((0, 2), 9)).unwrap();
+    assert_eq!(board.candidates((0, 2)), (1..=8).collect::<Vec<usize>>());
+}
+
+#[test]
+fn techniques_too_big_for_the_board() {
+    let mut board = SudokuBoard::from_grid(&[0; 256], Geometry::new(4, 4).unwrap()).unwrap();
+
+    assert!(matches!(
+        board.set_techniques(TechniqueSet::all()),
+        Result::Err(SudokuError::TechniqueNotSupported {
+            technique: Technique::AlternatingInferenceChain
+        })
+    ));
+    assert_eq!(board.techniques(), TechniqueSet::default());
+
+    let working: TechniqueSet = Technique::ALL
+        .into_iter()
+        .filter(|technique| technique.works_on(board.geometry()))
+        .collect();
+    assert!(working.contains(Technique::Jellyfish));
+    assert!(!working.contains(Technique::AlsXz));
+    board.set_techniques(working).unwrap();
+
+    // Solved, then with the first row taken away, so there's a little left
+    // for the rating to do
+    assert!(board.solve());
+    let mut grid: Vec<u8> = (0..256)
+        .map(|index| board.value((index / 16, index % 16)).unwrap() as u8)
+        .collect();
+    grid[..16].fill(0);
+
+    let board = SudokuBoard::from_grid(&grid, Geometry::new(4, 4).unwrap()).unwrap();
+    assert!(board.rate().is_ok());
+}
+
+#[test]
+fn invalid_character_position() {
+    let text = "
+        5 3 0 | 0 7 0 | 0 0 0
+        6 0 0 | 1 9 ? | 0 0 0";
+
+    // The first line is empty, and the '?' is after 14 spaces
+    match text.parse::<SudokuBoard>() {
+        Result::Err(SudokuError::InvalidCharacter {
+            character,
+            line,
+            column,
+            spaces_found,
+        }) => assert_eq!((character, line, column, spaces_found), ('?', 3, 21, 14)),
+        result => panic!("Expected InvalidCharacter, got {:?}", result),
+    }
+}
+
+#[test]
+fn invalid_character_position_in_numbers() {
+    // Boards with more than 9 values are read a number at a time, and the
+    // column is where the number starts. 17 is too big for 16x16
+    let text = "1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16\n  0 0 0 0 0 0 0 0 0 0 0 17 0";
+    let result = SudokuBoard::from_str_with_geometry(text, Geometry::new(4, 4).unwrap());
+
+    match result {
+        Result::Err(SudokuError::InvalidCharacter {
+            character,
+            line,
+            column,
+            spaces_found,
+        }) => assert_eq!((character, line, column, spaces_found), ('1', 2, 25, 27)),
+        result => panic!("Expected InvalidCharacter, got {:?}", result),
+    }
+}
+
+#[test]
+fn not_enough_spaces() {
+    match "0".repeat(80).parse::<SudokuBoard>() {
+        Result::Err(SudokuError::NotEnoughSpaces {
+            spaces_found,
+            spaces_expected,
+        }) => assert_eq!((spaces_found, spaces_expected), (80, 81)),
+        result => panic!("Expected NotEnoughSpaces, got {:?}", result),
+    }
+}
+
+#[test]
+fn duplicate_given_position() {
+    // Two 5s in the middle box, which aren't in the same row or column
+    let mut text = "0".repeat(81);
+    text.replace_range(30..31, "5");
+    text.replace_range(50..51, "5");
+    let mut board: SudokuBoard = text.parse().unwrap();
+
+    match board.try_solve() {
+        Result::Err(SudokuError::DuplicateGiven {
+            first,
+            second,
+            value,
+        }) => assert_eq!((first, second, value), ((3, 3), (5, 5), 5)),
+        result => panic!("Expected DuplicateGiven, got {:?}", result),
+    }
+}
+
+#[test]
+fn no_possible_values_position() {
+    // Row 3 has 1 to 8, and column 9 has a 9, so there's nothing left for
+    // row 3, column 9
+    let mut text = "0".repeat(81);
+    text.replace_range(18..26, "12345678");
+    text.replace_range(80..81, "9");
+    let mut board: SudokuBoard = text.parse().unwrap();
+
+    match board.try_solve() {
+        Result::Err(SudokuError::NoPossibleValues { point }) => assert_eq!(point, (2, 8)),
+        result => panic!("Expected NoPossibleValues, got {:?}", result),
+    }
+}
+
+#[test]
+fn diagonal_duplicate_given() {
+    // Two 5s on each diagonal, which aren't in the same row, column or box
+    for (first, second) in [(0, 80), (8, 72)] {
+        let mut text = "0".repeat(81);
+        text.replace_range(first..first + 1, "5");
+        text.replace_range(second..second + 1, "5");
+
+        let mut board: SudokuBoard = text.parse().unwrap();
+        assert!(board.try_solve().is_ok());
+
+        let mut board: SudokuBoard = format!("# diagonal\n{}", text).parse().unwrap();
+        assert!(board.is_diagonal());
+
+        match board.try_solve() {
+            Result::Err(SudokuError::DuplicateGiven {
+                first: first_point,
+                second: second_point,
+                value,
+            }) => assert_eq!(
+                (first_point, second_point, value),
+                ((first / 9, first % 9), (second / 9, second % 9), 5)
+            ),
+            result => panic!("Expected DuplicateGiven, got {:?}", result),
+        }
+    }
+}
+
+/// Returns the values on both diagonals of a solved 9x9 board, from the top
+fn diagonals(board: &SudokuBoard) -> [Vec<usize>; 2] {
+    return [
+        (0..9).map(|i| board.value((i, i)).unwrap()).collect(),
+        (0..9).map(|i| board.value((i, 8 - i)).unwrap()).collect(),
+    ];
+}
+
+#[test]
+fn diagonal_solutions() {
+    let mut board: SudokuBoard = format!("# diagonal\n{}", "0".repeat(81)).parse().unwrap();
+
+    let mut solved = board.clone();
+    assert!(solved.solve());
+    let mut boards = vec![solved];
+    boards.extend(board.solutions().take(10));
+
+    // And with every technique turned on
+    board.set_techniques(TechniqueSet::all()).unwrap();
+    let mut solved = board.clone();
+    assert!(solved.solve());
+    boards.push(solved);
+
+    for solved in boards {
+        assert!(solved.is_solved());
+
+        for mut diagonal in diagonals(&solved) {
+            diagonal.sort_unstable();
+            assert_eq!(diagonal, (1..=9).collect::<Vec<usize>>());
+        }
+    }
+}
@@ -0,0 +1,694 @@
+//! Solving step by step, and recording each step in a `Trace`
+//!
+//! A trace can be printed, with one step on each line, and parsed back.
+//! Replaying it onto the board it came from gives the same solution
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use crate::{
+    techniques, KnownSpaceQueue, SudokuBoard, SudokuError, SudokuValue, SudokuValueResult,
+    Technique, Unit,
+};
+
+/// Every step taken to solve a sudoku, in order. Made by
+/// `SudokuBoard::solve_with_trace`
+///
+/// ```
+/// use rsudoku::{SudokuBoard, Trace};
+///
+/// let board = SudokuBoard::from_file("boards/easy").unwrap();
+///
+/// let mut solved = board.clone();
+/// let trace = solved.solve_with_trace().unwrap();
+///
+/// // Traces can be saved as text, and read back
+/// let trace: Trace = trace.to_string().parse().unwrap();
+///
+/// let mut replayed = board.clone();
+/// replayed.replay(&trace).unwrap();
+/// assert_eq!(replayed.to_string(), solved.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+}
+
+/// A step of solving, and the changes it made to the board
+///
+/// Points are (row, column), starting at 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub kind: StepKind,
+
+    /// Spaces that were filled in, and their value
+    pub placements: Vec<((usize, usize), usize)>,
+
+    /// Possible values that were removed from spaces
+    pub eliminations: Vec<((usize, usize), usize)>,
+}
+
+/// Why a step was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepKind {
+    /// The givens were removed from their adjacent spaces
+    InitialPropagation,
+
+    /// A space had one possible value left
+    NakedSingle { space: (usize, usize) },
+
+    /// A space was the only one in a unit that could be a value
+    HiddenSingle { unit: Unit, space: (usize, usize) },
+
+    /// A constraint of the board ruled out possible values
+    Constraint,
+
+    /// A technique found a deduction
+    Technique(Technique),
+
+    /// Nothing else worked, so a value was guessed
+    Guess { space: (usize, usize), value: usize },
+
+    /// A guess was wrong. The board goes back to how it was before the
+    /// guess, and the guess is removed
+    Backtrack { space: (usize, usize), value: usize },
+}
+
+impl SudokuBoard {
+    /// Solves the sudoku, the same way as `try_solve`, but one step at a
+    /// time, and returns every step taken
+    ///
+    /// Naked singles are their own steps, after the step that left the
+    /// space with one possible value
+    pub fn solve_with_trace(&mut self) -> Result<Trace, SudokuError> {
+        let mut board = self.clone();
+        let mut steps = Vec::new();
+
+        // The board before each guess, and the guess
+        let mut guesses: Vec<(SudokuBoard, usize, usize)> = Vec::new();
+
+        let mut result = Result::Ok(());
+
+        if !board.initialized {
+            board.initialized = true;
+            result = board.check_duplicate_givens();
+
+            if result.is_ok() {
+                let givens: Vec<usize> = (0..board.geometry.spaces())
+                    .filter(|&index| board.get_space(index).is_known())
+                    .collect();
+
+                result =
+                    board.traced_apply(StepKind::InitialPropagation, &givens, &[], &[], &mut steps);
+            }
+        }
+
+        loop {
+            if let Result::Err(error) = result {
+                // Go back to before the last guess, and remove it
+                let (guess_board, index, value) = match guesses.pop() {
+                    Some(guess) => guess,
+                    None => return Result::Err(error),
+                };
+
+                board = guess_board;
+
+                let kind = StepKind::Backtrack {
+                    space: board.geometry.point_of(index),
+                    value,
+                };
+                result = board.traced_apply(kind, &[], &[], &[(index, value)], &mut steps);
+
+                continue;
+            }
+
+            if board.is_solved() {
+                *self = board;
+                return Result::Ok(Trace { steps });
+            }
+
+            result = board.traced_step(&mut guesses, &mut steps);
+        }
+    }
+
+    /// Takes the next step of solving: a hidden single, then anything the
+    /// constraints rule out, then a deduction, then a guess
+    fn traced_step(
+        &mut self,
+        guesses: &mut Vec<(SudokuBoard, usize, usize)>,
+        steps: &mut Vec<Step>,
+    ) -> Result<(), SudokuError> {
+        if let Some((unit, index, value)) = self.find_hidden_single()? {
+            let kind = StepKind::HiddenSingle {
+                unit: self.geometry.unit(unit),
+                space: self.geometry.point_of(index),
+            };
+
+            return self.traced_apply(kind, &[], &[(index, value)], &[], steps);
+        }
+
+        let eliminations = self.constraint_eliminations()?;
+
+        if !eliminations.is_empty() {
+            return self.traced_apply(StepKind::Constraint, &[], &[], &eliminations, steps);
+        }
+
+        for technique in self.techniques.iter() {
+            if let Some(deduction) = techniques::find(self, technique) {
+                let placements: Vec<(usize, usize)> = deduction
+                    .placements
+                    .iter()
+                    .map(|&(point, value)| (self.geometry.index_of(point), value))
+                    .collect();
+                let eliminations: Vec<(usize, usize)> = deduction
+                    .eliminations
+                    .iter()
+                    .map(|&(point, value)| (self.geometry.index_of(point), value))
+                    .collect();
+
+                let kind = StepKind::Technique(technique);
+                return self.traced_apply(kind, &[], &placements, &eliminations, steps);
+            }
+        }
+
+        let (index, value) = self.most_impactful_guess();
+        guesses.push((self.clone(), index, value));
+
+        let kind = StepKind::Guess {
+            space: self.geometry.point_of(index),
+            value,
+        };
+        return self.traced_apply(kind, &[], &[(index, value)], &[], steps);
+    }
+
+    /// Returns the first space that's the only one in a unit that can be a
+    /// value, like `narrow` looks for, as (unit, index, value)
+    ///
+    /// Returns `NoSpaceForValue` if a unit has no space for a value
+    pub(crate) fn find_hidden_single(&self) -> Result<Option<(usize, usize, usize)>, SudokuError> {
+        let units = self
+            .geometry
+            .units()
+            .iter()
+            .chain(&self.constraints.regions);
+
+        for (unit_index, unit) in units.enumerate() {
+            // Smaller regions don't have to have every value
+            if unit.len() != self.geometry.size() {
+                continue;
+            }
+
+            'values: for value in 1..=self.geometry.size() {
+                let mut space = None;
+
+                for &index in unit {
+                    match self.get_space(index) {
+                        SudokuValue::Known(known_value) => {
+                            if *known_value as usize == value {
+                                continue 'values;
+                            }
+                        }
+
+                        SudokuValue::Unknown(possible_values) => {
+                            if possible_values.contains(value) {
+                                if space.is_some() {
+                                    continue 'values;
+                                }
+
+                                space = Some(index);
+                            }
+                        }
+                    }
+                }
+
+                match space {
+                    None => {
+                        return Result::Err(SudokuError::NoSpaceForValue {
+                            unit: self.geometry.unit(unit_index),
+                            value,
+                        })
+                    }
+                    Some(index) => return Result::Ok(Some((unit_index, index, value))),
+                }
+            }
+        }
+
+        return Result::Ok(None);
+    }
+
+    /// Makes the changes of a step, and records them. `known` are spaces
+    /// that are already known, but still need removing from adjacent
+    /// spaces. Spaces that become known after that are recorded as naked
+    /// singles
+    ///
+    /// The step is recorded even if it makes the sudoku unsolvable
+    fn traced_apply(
+        &mut self,
+        kind: StepKind,
+        known: &[usize],
+        placements: &[(usize, usize)],
+        eliminations: &[(usize, usize)],
+        steps: &mut Vec<Step>,
+    ) -> Result<(), SudokuError> {
+        steps.push(Step {
+            kind,
+            placements: Vec::new(),
+            eliminations: Vec::new(),
+        });
+
+        let mut known_spaces_to_check = KnownSpaceQueue::new();
+
+        for &index in known {
+            known_spaces_to_check.push(index);
+        }
+
+        for &(index, value) in placements {
+            if self.set_known(index, value)? {
+                steps
+                    .last_mut()
+                    .unwrap()
+                    .placements
+                    .push((self.geometry.point_of(index), value));
+                known_spaces_to_check.push(index);
+            }
+        }
+
+        // Spaces added after this are naked singles
+        let step_spaces = known_spaces_to_check.end;
+
+        for &(index, value) in eliminations {
+            self.traced_remove(index, value, &mut known_spaces_to_check, steps)?;
+        }
+
+        // Shared, so the peers can be read while spaces change
+        let geometry = Arc::clone(&self.geometry);
+        let constraints = Arc::clone(&self.constraints);
+
+        while let Some(index) = known_spaces_to_check.pop() {
+            let value = match self.get_space(index) {
+                SudokuValue::Known(value) => *value as usize,
+                SudokuValue::Unknown(_) => panic!("Space should be Known"),
+            };
+
+            if known_spaces_to_check.start > step_spaces {
+                steps.push(Step {
+                    kind: StepKind::NakedSingle {
+                        space: geometry.point_of(index),
+                    },
+                    placements: vec![(geometry.point_of(index), value)],
+                    eliminations: Vec::new(),
+                });
+            }
+
+            for &adjacent_index in constraints.peers(&geometry, index) {
+                self.traced_remove(adjacent_index, value, &mut known_spaces_to_check, steps)?;
+            }
+        }
+
+        return Result::Ok(());
+    }
+
+    /// Removes a possible value from a space, and records it in the last
+    /// step. If the space becomes known, it's added to the queue
+    fn traced_remove(
+        &mut self,
+        index: usize,
+        value: usize,
+        known_spaces_to_check: &mut KnownSpaceQueue,
+        steps: &mut [Step],
+    ) -> Result<(), SudokuError> {
+        match self.remove_possible_value(index, value)? {
+            SudokuValueResult::PossibleValueAlreadyRemoved => {}
+            SudokuValueResult::ValueNowKnown => {
+                self.empty_spaces -= 1;
+                known_spaces_to_check.push(index);
+                steps
+                    .last_mut()
+                    .unwrap()
+                    .eliminations
+                    .push((self.geometry.point_of(index), value));
+            }
+            _ => steps
+                .last_mut()
+                .unwrap()
+                .eliminations
+                .push((self.geometry.point_of(index), value)),
+        }
+
+        return Result::Ok(());
+    }
+
+    /// Makes the changes recorded in a trace. Meant for the board the trace
+    /// was made from, before it was solved
+    ///
+    /// Returns an error if a change isn't possible, is off the board, or the
+    /// trace backtracks without a guess to go back to
+    pub fn replay(&mut self, trace: &Trace) -> Result<(), SudokuError> {
+        // The board before each guess
+        let mut guesses = Vec::new();
+
+        for (step_index, step) in trace.steps.iter().enumerate() {
+            match step.kind {
+                StepKind::InitialPropagation => self.initialized = true,
+                StepKind::Guess { .. } => guesses.push(self.clone()),
+                StepKind::Backtrack { .. } => match guesses.pop() {
+                    Some(guess_board) => *self = guess_board,
+                    None => {
+                        return Result::Err(SudokuError::InvalidStep {
+                            step: step_index + 1,
+                        })
+                    }
+                },
+                _ => {}
+            }
+
+            // Traces can be read from anywhere, so the changes might not fit
+            // on this board
+            let size = self.geometry.size();
+            let off_board = step
+                .placements
+                .iter()
+                .chain(&step.eliminations)
+                .any(|&((row, column), value)| row >= size || column >= size || value > size);
+
+            if off_board {
+                return Result::Err(SudokuError::InvalidStep {
+                    step: step_index + 1,
+                });
+            }
+
+            for &(point, value) in &step.placements {
+                self.set_known(self.geometry.index_of(point), value)?;
+            }
+
+            for &(point, value) in &step.eliminations {
+                if let SudokuValueResult::ValueNowKnown =
+                    self.remove_possible_value(self.geometry.index_of(point), value)?
+                {
+                    self.empty_spaces -= 1;
+                }
+            }
+        }
+
+        return Result::Ok(());
+    }
+}
+
+impl fmt::Display for Trace {
+    /// Prints each step on its own line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+
+        return Result::Ok(());
+    }
+}
+
+impl FromStr for Trace {
+    type Err = SudokuError;
+
+    /// Reads a trace printed by `Display`, with one step on each line.
+    /// Blank lines are skipped
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut steps = Vec::new();
+
+        for (line_index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match line.parse() {
+                Result::Ok(step) => steps.push(step),
+                Result::Err(_) => {
+                    return Result::Err(SudokuError::InvalidStep {
+                        step: line_index + 1,
+                    })
+                }
+            }
+        }
+
+        return Result::Ok(Trace { steps });
+    }
+}
+
+impl fmt::Display for Step {
+    /// Prints what the step was, then its changes, counting from 1. Like
+    /// "hidden single in row 3 at r3c4: r3c4=5 r3c7!=5"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            StepKind::InitialPropagation => write!(f, "initial propagation")?,
+            StepKind::NakedSingle { space } => write!(f, "naked single at {}", Space(space))?,
+            StepKind::HiddenSingle { unit, space } => {
+                write!(f, "hidden single in {} at {}", unit, Space(space))?
+            }
+            StepKind::Constraint => write!(f, "constraint")?,
+            StepKind::Technique(technique) => write!(f, "{}", technique)?,
+            StepKind::Guess { space, value } => write!(f, "guess {}={}", Space(space), value)?,
+            StepKind::Backtrack { space, value } => {
+                write!(f, "backtrack {}={}", Space(space), value)?
+            }
+        }
+
+        write!(f, ":")?;
+
+        for &(space, value) in &self.placements {
+            write!(f, " {}={}", Space(space), value)?;
+        }
+
+        for &(space, value) in &self.eliminations {
+            write!(f, " {}!={}", Space(space), value)?;
+        }
+
+        return Result::Ok(());
+    }
+}
+
+impl FromStr for Step {
+    type Err = SudokuError;
+
+    /// Reads a step printed by `Display`. The error is `InvalidStep`, for
+    /// step 1
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        return parse_step(text).ok_or(SudokuError::InvalidStep { step: 1 });
+    }
+}
+
+/// Reads a step, or returns None if it's invalid
+fn parse_step(text: &str) -> Option<Step> {
+    let (description, changes) = text.split_once(':')?;
+
+    let kind = if description == "initial propagation" {
+        StepKind::InitialPropagation
+    } else if description == "constraint" {
+        StepKind::Constraint
+    } else if let Some(space) = description.strip_prefix("naked single at ") {
+        StepKind::NakedSingle {
+            space: parse_space(space)?,
+        }
+    } else if let Some(rest) = description.strip_prefix("hidden single in ") {
+        let (unit, space) = rest.split_once(" at ")?;
+
+        StepKind::HiddenSingle {
+            unit: parse_unit(unit)?,
+            space: parse_space(space)?,
+        }
+    } else if let Some(guess) = description.strip_prefix("guess ") {
+        let (space, value) = parse_change(guess, "=")?;
+        StepKind::Guess { space, value }
+    } else if let Some(guess) = description.strip_prefix("backtrack ") {
+        let (space, value) = parse_change(guess, "=")?;
+        StepKind::Backtrack { space, value }
+    } else {
+        let technique = Technique::ALL
+            .into_iter()
+            .find(|technique| technique.name() == description)?;
+
+        StepKind::Technique(technique)
+    };
+
+    let mut step = Step {
+        kind,
+        placements: Vec::new(),
+        eliminations: Vec::new(),
+    };
+
+    for change in changes.split_whitespace() {
+        // "!=" has to be checked first, since it also has "="
+        if let Some(elimination) = parse_change(change, "!=") {
+            step.eliminations.push(elimination);
+        } else {
+            step.placements.push(parse_change(change, "=")?);
+        }
+    }
+
+    return Some(step);
+}
+
+/// Reads a space and value, like "r3c4=5", split by `separator`
+fn parse_change(text: &str, separator: &str) -> Option<((usize, usize), usize)> {
+    let (space, value) = text.split_once(separator)?;
+
+    let value = value.parse().ok().filter(|&value| value >= 1)?;
+
+    return Some((parse_space(space)?, value));
+}
+
+/// Reads a space like "r3c4", counting from 1
+fn parse_space(text: &str) -> Option<(usize, usize)> {
+    let (row, column) = text.strip_prefix('r')?.split_once('c')?;
+
+    let row: usize = row.parse().ok().filter(|&row| row >= 1)?;
+    let column: usize = column.parse().ok().filter(|&column| column >= 1)?;
+
+    return Some((row - 1, column - 1));
+}
+
+/// Reads a unit printed by its `Display`, like "row 3"
+fn parse_unit(text: &str) -> Option<Unit> {
+    let (kind, number) = text.split_once(' ')?;
+    let index = number.parse::<usize>().ok().filter(|&number| number >= 1)? - 1;
+
+    return match kind {
+        "row" => Some(Unit::Row(index)),
+        "column" => Some(Unit::Column(index)),
+        "box" => Some(Unit::Box(index)),
+        "region" => Some(Unit::Region(index)),
+        _ => None,
+    };
+}
+
+/// Prints a space like "r3c4", counting from 1
+struct Space((usize, usize));
+
+impl fmt::Display for Space {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}c{}", self.0 .0 + 1, self.0 .1 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{Relation, SudokuBoard, SudokuError, Sum};
+
+    use super::{StepKind, Trace};
+
+    /// Solves the board with a trace, prints the trace and reads it back,
+    /// then replays it onto the board. Checks the replay ends up solved the
+    /// same way
+    fn round_trip(board: &SudokuBoard) {
+        let mut solved = board.clone();
+        let trace = solved.solve_with_trace().unwrap();
+        assert!(solved.is_solved());
+
+        let read: Trace = trace.to_string().parse().unwrap();
+        assert_eq!(read, trace);
+
+        let mut replayed = board.clone();
+        replayed.replay(&read).unwrap();
+        assert!(replayed.is_solved());
+        assert_eq!(replayed.to_string(), solved.to_string());
+    }
+
+    #[test]
+    fn bundled_boards_round_trip() {
+        for entry in fs::read_dir("boards").unwrap() {
+            let path = entry.unwrap().path();
+            let board = SudokuBoard::from_file(&path).unwrap();
+
+            if path.ends_with("unsolveable") {
+                assert!(board.clone().solve_with_trace().is_err());
+            } else {
+                round_trip(&board);
+            }
+        }
+    }
+
+    #[test]
+    fn constrained_board_round_trip() {
+        let mut board: SudokuBoard = "0".repeat(81).parse().unwrap();
+        board.set_diagonal(true);
+        board
+            .add_constraint(Relation::less_than((0, 1), (0, 0)))
+            .unwrap();
+        board
+            .add_constraint(Sum::new(vec![(8, 7), (8, 8)], 3))
+            .unwrap();
+
+        round_trip(&board);
+
+        let mut solved = board.clone();
+        solved.solve_with_trace().unwrap();
+        assert!(solved.value((0, 1)) < solved.value((0, 0)));
+        assert_eq!(
+            solved.value((8, 7)).unwrap() + solved.value((8, 8)).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn replay_rejects_a_wrong_value() {
+        let board = SudokuBoard::from_file("boards/easy").unwrap();
+        let mut trace = board.clone().solve_with_trace().unwrap();
+
+        // A naked single's space had its other values removed by earlier
+        // steps, so placing one of them can't work
+        let single = trace
+            .steps
+            .iter()
+            .position(|step| matches!(step.kind, StepKind::NakedSingle { .. }))
+            .unwrap();
+        let (point, value) = trace.steps[single].placements[0];
+        let wrong = trace.steps[..single]
+            .iter()
+            .flat_map(|step| &step.eliminations)
+            .find(|&&(eliminated, other)| eliminated == point && other != value)
+            .unwrap()
+            .1;
+        trace.steps[single].placements[0] = (point, wrong);
+
+        let mut replayed = board.clone();
+        assert!(matches!(
+            replayed.replay(&trace),
+            Result::Err(SudokuError::ValueNotPossible { point: p, value: v })
+                if p == point && v == wrong
+        ));
+    }
+
+    #[test]
+    fn replay_rejects_a_bad_step() {
+        let board = SudokuBoard::from_file("boards/easy").unwrap();
+
+        // A backtrack with no guess before it
+        let trace: Trace = "initial propagation:\nbacktrack r1c3=4:".parse().unwrap();
+        assert!(matches!(
+            board.clone().replay(&trace),
+            Result::Err(SudokuError::InvalidStep { step: 2 })
+        ));
+
+        // A change to a space that isn't on the board
+        let trace: Trace = "initial propagation: r1c10!=4".parse().unwrap();
+        assert!(matches!(
+            board.clone().replay(&trace),
+            Result::Err(SudokuError::InvalidStep { step: 1 })
+        ));
+
+        // A value that's too big
+        let trace: Trace = "initial propagation: r1c3!=10".parse().unwrap();
+        assert!(matches!(
+            board.clone().replay(&trace),
+            Result::Err(SudokuError::InvalidStep { step: 1 })
+        ));
+    }
+
+    #[test]
+    fn unreadable_steps() {
+        let text = "initial propagation: r1c3!=5\n\nnot a step: r1c1=1";
+
+        assert!(matches!(
+            text.parse::<Trace>(),
+            Result::Err(SudokuError::InvalidStep { step: 3 })
+        ));
+    }
+}